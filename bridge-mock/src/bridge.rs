@@ -0,0 +1,60 @@
+#![no_std]
+
+multiversx_sc::imports!();
+multiversx_sc::derive_imports!();
+
+pub type Epoch = u64;
+pub const SNAPSHOT_FORMAT_VERSION: u8 = 1;
+
+const BLS_KEY_LEN: usize = 96;
+
+pub type BlsKey<M> = ManagedByteArray<M, BLS_KEY_LEN>;
+
+// Same encoding as the snapshot sent by gravity-restaking
+#[derive(TypeAbi, TopEncode, TopDecode, NestedEncode, NestedDecode, ManagedVecItem)]
+pub struct ValidatorSecurity<M: ManagedTypeApi> {
+    pub validator: ManagedAddress<M>,
+    pub stake: BigUint<M>,
+    pub bls_keys: ManagedVec<M, BlsKey<M>>,
+}
+
+#[derive(TypeAbi, TopEncode, TopDecode, NestedEncode, NestedDecode)]
+pub struct SecuritySnapshot<M: ManagedTypeApi> {
+    pub version: u8,
+    pub nonce: u64,
+    pub epoch: Epoch,
+    pub total_stake: BigUint<M>,
+    pub validators: ManagedVec<M, ValidatorSecurity<M>>,
+}
+
+#[multiversx_sc::derive::contract]
+pub trait BridgeMock {
+    #[init]
+    fn init(&self) {}
+
+    #[endpoint(receiveSecuritySnapshot)]
+    fn receive_security_snapshot(&self, snapshot: SecuritySnapshot<Self::Api>) {
+        require!(
+            snapshot.version == SNAPSHOT_FORMAT_VERSION,
+            "Unknown snapshot version"
+        );
+
+        let caller = self.blockchain().get_caller();
+        let last_snapshot_mapper = self.last_snapshot(&caller);
+        if !last_snapshot_mapper.is_empty() {
+            require!(
+                snapshot.nonce > last_snapshot_mapper.get().nonce,
+                "Old snapshot"
+            );
+        }
+
+        last_snapshot_mapper.set(snapshot);
+    }
+
+    #[view(getLastSnapshot)]
+    #[storage_mapper("lastSnapshot")]
+    fn last_snapshot(
+        &self,
+        sender: &ManagedAddress,
+    ) -> SingleValueMapper<SecuritySnapshot<Self::Api>>;
+}
@@ -1,17 +1,81 @@
 #![no_std]
 
 multiversx_sc::imports!();
+multiversx_sc::derive_imports!();
 
 pub type Epoch = u64;
 pub const MAX_PERCENTAGE: u64 = 100_000;
-pub const APY: u64 = 10_000; //10%
-pub const EPOCHS_IN_YEAR: u64 = 365;
-pub const UNBOND_PERIOD: u64 = 10;
+pub const DEFAULT_APY: u64 = 10_000; //10%
+pub const DEFAULT_EPOCHS_IN_YEAR: u64 = 365;
+pub const DEFAULT_UNBOND_PERIOD: u64 = 10;
+
+#[derive(TypeAbi, TopEncode, TopDecode, NestedEncode, NestedDecode, ManagedVecItem, Clone)]
+pub struct Undelegation<M: ManagedTypeApi> {
+    pub amount: BigUint<M>,
+    pub unbond_epoch: Epoch,
+}
+
+/// Rates in effect until the given epoch, replaced since
+#[derive(TypeAbi, TopEncode, TopDecode)]
+pub struct RatePeriod {
+    pub end_epoch: Epoch,
+    pub apy: u64,
+    pub epochs_in_year: Epoch,
+}
+
+#[derive(TypeAbi, TopEncode, TopDecode)]
+pub struct UserInfo<M: ManagedTypeApi> {
+    pub deposit: BigUint<M>,
+    pub undelegations: ManagedVec<M, Undelegation<M>>,
+    pub unclaimed_rewards: BigUint<M>,
+    pub last_claim_epoch: Epoch,
+}
 
 #[multiversx_sc::derive::contract]
 pub trait DelegationMock {
     #[init]
-    fn init(&self) {}
+    fn init(&self) {
+        self.apy().set_if_empty(DEFAULT_APY);
+        self.epochs_in_year().set_if_empty(DEFAULT_EPOCHS_IN_YEAR);
+        self.unbond_period().set_if_empty(DEFAULT_UNBOND_PERIOD);
+    }
+
+    #[only_owner]
+    #[endpoint(setApy)]
+    fn set_apy(&self, apy: u64) {
+        require!(apy <= MAX_PERCENTAGE, "Invalid APY");
+
+        self.end_rate_period();
+        self.apy().set(apy);
+    }
+
+    #[only_owner]
+    #[endpoint(setEpochsInYear)]
+    fn set_epochs_in_year(&self, epochs_in_year: Epoch) {
+        require!(epochs_in_year > 0, "Invalid epochs in year");
+
+        self.end_rate_period();
+        self.epochs_in_year().set(epochs_in_year);
+    }
+
+    #[only_owner]
+    #[endpoint(setUnbondPeriod)]
+    fn set_unbond_period(&self, unbond_period: Epoch) {
+        self.unbond_period().set(unbond_period);
+    }
+
+    /// One-time seed of the delegated total with the deposits and undelegations made before it was tracked
+    #[only_owner]
+    #[endpoint(seedTotalDelegated)]
+    fn seed_total_delegated(&self, amount: BigUint) {
+        require!(
+            !self.total_delegated_seeded().get(),
+            "Total delegated already seeded"
+        );
+        self.total_delegated_seeded().set(true);
+
+        self.total_delegated().update(|value| *value += amount);
+    }
 
     #[payable("EGLD")]
     #[only_owner]
@@ -27,12 +91,30 @@ pub trait DelegationMock {
     fn delegate(&self) {
         let caller = self.blockchain().get_caller();
         let payment_amount = self.call_value().egld_value().clone_value();
+        self.settle_rewards(&caller);
         self.address_deposit(&caller)
             .update(|value| *value += &payment_amount);
+        self.total_delegated()
+            .update(|value| *value += &payment_amount);
         self.egld_token_supply()
             .update(|value| *value += &payment_amount);
     }
 
+    /// Moves the user's delegated stake to the caller, i.e. the restaking contract
+    #[endpoint(moveStakeToReStaking)]
+    fn move_stake_to_re_staking(&self, user: ManagedAddress, value: BigUint) {
+        let caller = self.blockchain().get_caller();
+        let user_deposit = self.address_deposit(&user).get();
+        require!(value > 0 && value <= user_deposit, "Invalid amount to move");
+
+        self.settle_rewards(&user);
+        self.settle_rewards(&caller);
+        self.address_deposit(&user)
+            .update(|deposit| *deposit -= &value);
+        self.address_deposit(&caller)
+            .update(|deposit| *deposit += &value);
+    }
+
     #[endpoint(unDelegate)]
     fn undelegate(&self, egld_to_undelegate: BigUint) {
         let caller = self.blockchain().get_caller();
@@ -42,31 +124,46 @@ pub trait DelegationMock {
             egld_to_undelegate > 0 && egld_to_undelegate <= total_deposit,
             "Invalid undelegate amount"
         );
+        self.settle_rewards(&caller);
+        self.migrate_legacy_undelegation(&caller);
         self.address_deposit(&caller)
             .update(|value| *value -= &egld_to_undelegate);
-        self.address_undelegate_amount(&caller)
-            .update(|value| *value += &egld_to_undelegate);
-        self.address_undelegate_epoch(&caller)
-            .set(current_epoch + UNBOND_PERIOD);
+
+        let undelegation = Undelegation {
+            amount: egld_to_undelegate,
+            unbond_epoch: current_epoch + self.unbond_period().get(),
+        };
+        self.address_undelegations(&caller)
+            .update(|undelegations| undelegations.push(undelegation));
     }
 
+    /// Withdraws all the undelegations whose unbond period has passed
     #[endpoint(withdraw)]
     fn withdraw(&self) {
         let caller = self.blockchain().get_caller();
         let current_epoch = self.blockchain().get_block_epoch();
-        let withdraw_epoch = self.address_undelegate_epoch(&caller).get();
-        let withdraw_amount = self.address_undelegate_amount(&caller).get();
+        self.migrate_legacy_undelegation(&caller);
+
+        let mut withdraw_amount = BigUint::zero();
+        self.address_undelegations(&caller).update(|undelegations| {
+            let mut remaining = ManagedVec::new();
+            for undelegation in undelegations.iter() {
+                if current_epoch >= undelegation.unbond_epoch {
+                    withdraw_amount += undelegation.amount;
+                } else {
+                    remaining.push(undelegation);
+                }
+            }
+
+            *undelegations = remaining;
+        });
 
         require!(withdraw_amount > 0, "No amount to withdraw");
-        require!(
-            withdraw_epoch > 0 && current_epoch >= withdraw_epoch,
-            "Cannot withdraw yet"
-        );
 
+        self.total_delegated()
+            .update(|value| *value -= &withdraw_amount);
         self.egld_token_supply()
             .update(|value| *value -= &withdraw_amount);
-        self.address_undelegate_epoch(&caller).clear();
-        self.address_undelegate_amount(&caller).clear();
 
         self.send_raw().async_call_raw(
             &caller,
@@ -76,37 +173,183 @@ pub trait DelegationMock {
         );
     }
 
+    /// If the reward pool can't cover the rewards, what is available is paid out
+    /// and the rest is kept for the next claim
     #[endpoint(claimRewards)]
     fn claim_rewards(&self) {
         let caller = self.blockchain().get_caller();
+        self.settle_rewards(&caller);
+
+        let rewards = self.address_unclaimed_rewards(&caller).get();
+        let reward_pool = self.get_reward_pool();
+        let paid_rewards = if rewards > reward_pool {
+            reward_pool
+        } else {
+            rewards.clone()
+        };
+        self.address_unclaimed_rewards(&caller)
+            .set(&(rewards - &paid_rewards));
+
+        if paid_rewards > 0u64 {
+            self.egld_token_supply()
+                .update(|value| *value -= &paid_rewards);
+            self.send().direct_egld(&caller, &paid_rewards);
+        }
+    }
+
+    #[view(getUserInfo)]
+    fn get_user_info(&self, address: ManagedAddress) -> UserInfo<Self::Api> {
         let current_epoch = self.blockchain().get_block_epoch();
-        let last_claim_epoch = self.address_last_claim_epoch(&caller).get();
-        let total_deposit = self.address_deposit(&caller).get();
+        let last_claim_epoch = self.address_last_claim_epoch(&address).get();
+        let mut unclaimed_rewards = self.address_unclaimed_rewards(&address).get();
+        if current_epoch > last_claim_epoch {
+            unclaimed_rewards += self.compute_rewards(&address, last_claim_epoch, current_epoch);
+        }
+
+        let mut undelegations = self.address_undelegations(&address).get();
+        if let Some(legacy_undelegation) = self.get_legacy_undelegation(&address) {
+            undelegations.push(legacy_undelegation);
+        }
+
+        UserInfo {
+            deposit: self.address_deposit(&address).get(),
+            undelegations,
+            unclaimed_rewards,
+            last_claim_epoch,
+        }
+    }
 
+    /// EGLD that is not owed to delegators
+    #[view(getRewardPool)]
+    fn get_reward_pool(&self) -> BigUint {
+        let egld_token_supply = self.egld_token_supply().get();
+        let total_delegated = self.total_delegated().get();
+        if egld_token_supply <= total_delegated {
+            return BigUint::zero();
+        }
+
+        egld_token_supply - total_delegated
+    }
+
+    /// Rewards are computed on the current deposit, so they're settled before every deposit change
+    fn settle_rewards(&self, address: &ManagedAddress) {
+        let current_epoch = self.blockchain().get_block_epoch();
+        let last_claim_mapper = self.address_last_claim_epoch(address);
+        let last_claim_epoch = last_claim_mapper.get();
         if current_epoch > last_claim_epoch {
-            let rewards = (total_deposit * APY / MAX_PERCENTAGE)
-                * (current_epoch - last_claim_epoch)
-                / EPOCHS_IN_YEAR;
-            if rewards > 0u64 {
-                self.send().direct_egld(&caller, &rewards);
-                self.address_last_claim_epoch(&caller).set(current_epoch);
-                self.egld_token_supply().update(|value| *value -= &rewards);
+            let rewards = self.compute_rewards(address, last_claim_epoch, current_epoch);
+            self.address_unclaimed_rewards(address)
+                .update(|unclaimed_rewards| *unclaimed_rewards += rewards);
+        }
+
+        last_claim_mapper.set(current_epoch);
+    }
+
+    /// Each epoch earns at the rates in effect at that time
+    fn compute_rewards(
+        &self,
+        address: &ManagedAddress,
+        from_epoch: Epoch,
+        to_epoch: Epoch,
+    ) -> BigUint {
+        let total_deposit = self.address_deposit(address).get();
+        let mut rewards = BigUint::zero();
+        let mut start_epoch = from_epoch;
+        for rate_period in self.rate_periods().iter() {
+            if rate_period.end_epoch <= start_epoch {
+                continue;
+            }
+
+            let end_epoch = core::cmp::min(rate_period.end_epoch, to_epoch);
+            rewards += (&total_deposit * rate_period.apy / MAX_PERCENTAGE)
+                * (end_epoch - start_epoch)
+                / rate_period.epochs_in_year;
+            start_epoch = end_epoch;
+            if start_epoch == to_epoch {
+                return rewards;
             }
         }
+
+        rewards
+            + (total_deposit * self.apy().get() / MAX_PERCENTAGE) * (to_epoch - start_epoch)
+                / self.epochs_in_year().get()
+    }
+
+    /// Keeps the rates in effect so far for the rewards not settled yet
+    fn end_rate_period(&self) {
+        self.rate_periods().push(&RatePeriod {
+            end_epoch: self.blockchain().get_block_epoch(),
+            apy: self.apy().get(),
+            epochs_in_year: self.epochs_in_year().get(),
+        });
+    }
+
+    /// Moves the single undelegation kept before per-undelegation tracking into the list
+    fn migrate_legacy_undelegation(&self, address: &ManagedAddress) {
+        if let Some(legacy_undelegation) = self.get_legacy_undelegation(address) {
+            self.address_undelegations(address)
+                .update(|undelegations| undelegations.push(legacy_undelegation));
+        }
+
+        self.legacy_undelegate_amount(address).clear();
+        self.legacy_undelegate_epoch(address).clear();
+    }
+
+    fn get_legacy_undelegation(&self, address: &ManagedAddress) -> Option<Undelegation<Self::Api>> {
+        let amount = self.legacy_undelegate_amount(address).get();
+        if amount == 0 {
+            return None;
+        }
+
+        Some(Undelegation {
+            amount,
+            unbond_epoch: self.legacy_undelegate_epoch(address).get(),
+        })
     }
 
+    #[view(getApy)]
+    #[storage_mapper("apy")]
+    fn apy(&self) -> SingleValueMapper<u64>;
+
+    #[view(getEpochsInYear)]
+    #[storage_mapper("epochsInYear")]
+    fn epochs_in_year(&self) -> SingleValueMapper<Epoch>;
+
+    #[view(getUnbondPeriod)]
+    #[storage_mapper("unbondPeriod")]
+    fn unbond_period(&self) -> SingleValueMapper<Epoch>;
+
+    #[storage_mapper("ratePeriods")]
+    fn rate_periods(&self) -> VecMapper<RatePeriod>;
+
     #[storage_mapper("egldTokenSupply")]
     fn egld_token_supply(&self) -> SingleValueMapper<BigUint>;
 
+    /// Deposits and undelegated amounts not yet withdrawn
+    #[storage_mapper("totalDelegated")]
+    fn total_delegated(&self) -> SingleValueMapper<BigUint>;
+
+    #[storage_mapper("totalDelegatedSeeded")]
+    fn total_delegated_seeded(&self) -> SingleValueMapper<bool>;
+
     #[storage_mapper("addressDeposit")]
     fn address_deposit(&self, address: &ManagedAddress) -> SingleValueMapper<BigUint>;
 
     #[storage_mapper("addressLastClaim")]
     fn address_last_claim_epoch(&self, address: &ManagedAddress) -> SingleValueMapper<Epoch>;
 
+    #[storage_mapper("addressUnclaimedRewards")]
+    fn address_unclaimed_rewards(&self, address: &ManagedAddress) -> SingleValueMapper<BigUint>;
+
     #[storage_mapper("addressUndelegateAmount")]
-    fn address_undelegate_amount(&self, address: &ManagedAddress) -> SingleValueMapper<BigUint>;
+    fn legacy_undelegate_amount(&self, address: &ManagedAddress) -> SingleValueMapper<BigUint>;
 
     #[storage_mapper("addressUndelegateEpoch")]
-    fn address_undelegate_epoch(&self, address: &ManagedAddress) -> SingleValueMapper<Epoch>;
+    fn legacy_undelegate_epoch(&self, address: &ManagedAddress) -> SingleValueMapper<Epoch>;
+
+    #[storage_mapper("addressUndelegations")]
+    fn address_undelegations(
+        &self,
+        address: &ManagedAddress,
+    ) -> SingleValueMapper<ManagedVec<Undelegation<Self::Api>>>;
 }
@@ -5,9 +5,9 @@
 ////////////////////////////////////////////////////
 
 // Init:                                 1
-// Endpoints:                            5
+// Endpoints:                           15
 // Async Callback (empty):               1
-// Total number of exported functions:   7
+// Total number of exported functions:  17
 
 #![no_std]
 #![allow(internal_features)]
@@ -20,11 +20,21 @@ multiversx_sc_wasm_adapter::endpoints! {
     delegation_mock
     (
         init => init
+        setApy => set_apy
+        setEpochsInYear => set_epochs_in_year
+        setUnbondPeriod => set_unbond_period
+        seedTotalDelegated => seed_total_delegated
         depositEGLD => deposit_egld
         delegate => delegate
+        moveStakeToReStaking => move_stake_to_re_staking
         unDelegate => undelegate
         withdraw => withdraw
         claimRewards => claim_rewards
+        getUserInfo => get_user_info
+        getRewardPool => get_reward_pool
+        getApy => apy
+        getEpochsInYear => epochs_in_year
+        getUnbondPeriod => unbond_period
     )
 }
 
@@ -1,3 +1,4 @@
 pub mod sov_events;
+pub mod treasury_events;
 pub mod user_events;
 pub mod validator_events;
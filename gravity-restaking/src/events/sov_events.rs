@@ -1,4 +1,7 @@
-use crate::{unique_payments::PaymentsVec, user_actions::sovereign::SovereignInfo};
+use crate::{
+    unique_payments::PaymentsVec,
+    user_actions::sovereign::{Epoch, SovState, SovereignInfo},
+};
 
 multiversx_sc::imports!();
 
@@ -13,11 +16,30 @@ pub trait SovEventsModule {
         self.sov_register_event(sov_address, sov_info);
     }
 
+    #[inline]
+    fn emit_sov_update_info_event(
+        &self,
+        sov_address: ManagedAddress,
+        sov_info: SovereignInfo<Self::Api>,
+    ) {
+        self.sov_update_info_event(sov_address, sov_info);
+    }
+
     #[inline]
     fn emit_sov_unregister_event(&self, sov_address: ManagedAddress) {
         self.sov_unregister_event(sov_address);
     }
 
+    #[inline]
+    fn emit_sov_state_change_event(
+        &self,
+        sov_address: ManagedAddress,
+        old_state: SovState,
+        new_state: SovState,
+    ) {
+        self.sov_state_change_event(sov_address, old_state, new_state);
+    }
+
     #[inline]
     fn emit_sov_add_own_security_funds_event(
         &self,
@@ -27,11 +49,110 @@ pub trait SovEventsModule {
         self.sov_add_own_security_funds_event(sov_address, payments);
     }
 
+    #[inline]
+    fn emit_sov_withdraw_own_security_funds_event(
+        &self,
+        sov_address: ManagedAddress,
+        payments: PaymentsVec<Self::Api>,
+    ) {
+        self.sov_withdraw_own_security_funds_event(sov_address, payments);
+    }
+
     #[inline]
     fn emit_sov_set_max_restaking_cap_event(&self, sov_address: ManagedAddress, max: BigUint) {
         self.sov_set_max_restaking_cap_event(sov_address, max);
     }
 
+    #[inline]
+    fn emit_sov_set_accepted_tokens_event(
+        &self,
+        sov_address: ManagedAddress,
+        tokens: ManagedVec<TokenIdentifier>,
+    ) {
+        self.sov_set_accepted_tokens_event(sov_address, tokens);
+    }
+
+    #[inline]
+    fn emit_sov_set_min_delegation_event(&self, sov_address: ManagedAddress, min: BigUint) {
+        self.sov_set_min_delegation_event(sov_address, min);
+    }
+
+    #[inline]
+    fn emit_sov_set_min_total_security_event(&self, sov_address: ManagedAddress, min: BigUint) {
+        self.sov_set_min_total_security_event(sov_address, min);
+    }
+
+    #[inline]
+    fn emit_sov_set_target_validator_count_event(
+        &self,
+        sov_address: ManagedAddress,
+        target_validator_count: usize,
+    ) {
+        self.sov_set_target_validator_count_event(sov_address, target_validator_count);
+    }
+
+    #[inline]
+    fn emit_sov_set_allow_list_enabled_event(&self, sov_address: ManagedAddress, enabled: bool) {
+        self.sov_set_allow_list_enabled_event(sov_address, enabled);
+    }
+
+    #[inline]
+    fn emit_sov_add_to_allow_list_event(
+        &self,
+        sov_address: ManagedAddress,
+        addresses: ManagedVec<ManagedAddress>,
+    ) {
+        self.sov_add_to_allow_list_event(sov_address, addresses);
+    }
+
+    #[inline]
+    fn emit_sov_remove_from_allow_list_event(
+        &self,
+        sov_address: ManagedAddress,
+        addresses: ManagedVec<ManagedAddress>,
+    ) {
+        self.sov_remove_from_allow_list_event(sov_address, addresses);
+    }
+
+    #[inline]
+    fn emit_sov_set_max_per_delegator_event(&self, sov_address: ManagedAddress, max: BigUint) {
+        self.sov_set_max_per_delegator_event(sov_address, max);
+    }
+
+    #[inline]
+    fn emit_sov_set_security_bridge_event(
+        &self,
+        sov_address: ManagedAddress,
+        bridge: ManagedAddress,
+    ) {
+        self.sov_set_security_bridge_event(sov_address, bridge);
+    }
+
+    #[inline]
+    fn emit_sov_security_snapshot_event(
+        &self,
+        sov_address: ManagedAddress,
+        nonce: u64,
+        epoch: Epoch,
+        total_stake: BigUint,
+    ) {
+        self.sov_security_snapshot_event(sov_address, nonce, epoch, total_stake);
+    }
+
+    #[inline]
+    fn emit_sov_propose_owner_event(&self, sov_address: ManagedAddress, new_owner: ManagedAddress) {
+        self.sov_propose_owner_event(sov_address, new_owner);
+    }
+
+    #[inline]
+    fn emit_sov_transfer_ownership_event(
+        &self,
+        old_owner: ManagedAddress,
+        new_owner: ManagedAddress,
+    ) {
+        self.sov_transfer_ownership_event(old_owner, new_owner);
+    }
+
     // Events
 
     #[event("sovRegisterEvent")]
@@ -41,6 +162,21 @@ pub trait SovEventsModule {
         sov_info: SovereignInfo<Self::Api>,
     );
 
+    #[event("sovUpdateInfoEvent")]
+    fn sov_update_info_event(
+        &self,
+        #[indexed] sov_address: ManagedAddress,
+        sov_info: SovereignInfo<Self::Api>,
+    );
+
+    #[event("sovStateChangeEvent")]
+    fn sov_state_change_event(
+        &self,
+        #[indexed] sov_address: ManagedAddress,
+        old_state: SovState,
+        new_state: SovState,
+    );
+
     #[event("sovUnregisterEvent")]
     fn sov_unregister_event(&self, #[indexed] sov_address: ManagedAddress);
 
@@ -51,6 +187,91 @@ pub trait SovEventsModule {
         payments: PaymentsVec<Self::Api>,
     );
 
+    #[event("sovWithdrawOwnSecurityFundsEvent")]
+    fn sov_withdraw_own_security_funds_event(
+        &self,
+        #[indexed] sov_address: ManagedAddress,
+        payments: PaymentsVec<Self::Api>,
+    );
+
     #[event("sovSetMaxRestakingCapEvent")]
     fn sov_set_max_restaking_cap_event(&self, #[indexed] sov_address: ManagedAddress, max: BigUint);
+
+    #[event("sovSetAcceptedTokensEvent")]
+    fn sov_set_accepted_tokens_event(
+        &self,
+        #[indexed] sov_address: ManagedAddress,
+        tokens: ManagedVec<TokenIdentifier>,
+    );
+
+    #[event("sovSetMinDelegationEvent")]
+    fn sov_set_min_delegation_event(&self, #[indexed] sov_address: ManagedAddress, min: BigUint);
+
+    #[event("sovSetMinTotalSecurityEvent")]
+    fn sov_set_min_total_security_event(
+        &self,
+        #[indexed] sov_address: ManagedAddress,
+        min: BigUint,
+    );
+
+    #[event("sovSetTargetValidatorCountEvent")]
+    fn sov_set_target_validator_count_event(
+        &self,
+        #[indexed] sov_address: ManagedAddress,
+        target_validator_count: usize,
+    );
+
+    #[event("sovSetAllowListEnabledEvent")]
+    fn sov_set_allow_list_enabled_event(
+        &self,
+        #[indexed] sov_address: ManagedAddress,
+        enabled: bool,
+    );
+
+    #[event("sovAddToAllowListEvent")]
+    fn sov_add_to_allow_list_event(
+        &self,
+        #[indexed] sov_address: ManagedAddress,
+        addresses: ManagedVec<ManagedAddress>,
+    );
+
+    #[event("sovRemoveFromAllowListEvent")]
+    fn sov_remove_from_allow_list_event(
+        &self,
+        #[indexed] sov_address: ManagedAddress,
+        addresses: ManagedVec<ManagedAddress>,
+    );
+
+    #[event("sovSetMaxPerDelegatorEvent")]
+    fn sov_set_max_per_delegator_event(&self, #[indexed] sov_address: ManagedAddress, max: BigUint);
+
+    #[event("sovSetSecurityBridgeEvent")]
+    fn sov_set_security_bridge_event(
+        &self,
+        #[indexed] sov_address: ManagedAddress,
+        #[indexed] bridge: ManagedAddress,
+    );
+
+    #[event("sovSecuritySnapshotEvent")]
+    fn sov_security_snapshot_event(
+        &self,
+        #[indexed] sov_address: ManagedAddress,
+        #[indexed] nonce: u64,
+        epoch: Epoch,
+        total_stake: BigUint,
+    );
+
+    #[event("sovProposeOwnerEvent")]
+    fn sov_propose_owner_event(
+        &self,
+        #[indexed] sov_address: ManagedAddress,
+        #[indexed] new_owner: ManagedAddress,
+    );
+
+    #[event("sovTransferOwnershipEvent")]
+    fn sov_transfer_ownership_event(
+        &self,
+        #[indexed] old_owner: ManagedAddress,
+        #[indexed] new_owner: ManagedAddress,
+    );
 }
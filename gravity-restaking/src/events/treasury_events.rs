@@ -0,0 +1,57 @@
+use crate::{unique_payments::PaymentsVec, user_actions::validator::Percent};
+
+multiversx_sc::imports!();
+
+#[multiversx_sc::module]
+pub trait TreasuryEventsModule {
+    #[inline]
+    fn emit_set_protocol_fee_event(&self, protocol_fee: Percent) {
+        self.set_protocol_fee_event(protocol_fee);
+    }
+
+    #[inline]
+    fn emit_set_treasury_event(&self, treasury: ManagedAddress) {
+        self.set_treasury_event(treasury);
+    }
+
+    #[inline]
+    fn emit_protocol_fee_accrued_event(&self, fee_payment: EsdtTokenPayment) {
+        self.protocol_fee_accrued_event(
+            fee_payment.token_identifier,
+            fee_payment.token_nonce,
+            fee_payment.amount,
+        );
+    }
+
+    #[inline]
+    fn emit_claim_protocol_fees_event(
+        &self,
+        treasury: ManagedAddress,
+        payments: PaymentsVec<Self::Api>,
+    ) {
+        self.claim_protocol_fees_event(treasury, payments);
+    }
+
+    // Events
+
+    #[event("setProtocolFeeEvent")]
+    fn set_protocol_fee_event(&self, protocol_fee: Percent);
+
+    #[event("setTreasuryEvent")]
+    fn set_treasury_event(&self, #[indexed] treasury: ManagedAddress);
+
+    #[event("protocolFeeAccruedEvent")]
+    fn protocol_fee_accrued_event(
+        &self,
+        #[indexed] token_id: TokenIdentifier,
+        #[indexed] token_nonce: u64,
+        amount: BigUint,
+    );
+
+    #[event("claimProtocolFeesEvent")]
+    fn claim_protocol_fees_event(
+        &self,
+        #[indexed] treasury: ManagedAddress,
+        payments: PaymentsVec<Self::Api>,
+    );
+}
@@ -1,4 +1,7 @@
-use crate::unique_payments::{PaymentsVec, UniquePayments};
+use crate::{
+    unique_payments::{PaymentsVec, UniquePayments},
+    user_actions::validator::Percent,
+};
 
 multiversx_sc::imports!();
 
@@ -59,6 +62,77 @@ pub trait UserEventsModule {
         self.revoke_sov_event(caller, sov, payments);
     }
 
+    #[inline]
+    fn emit_redelegate_validator_event(
+        &self,
+        caller: ManagedAddress,
+        from_validator: ManagedAddress,
+        to_validator: ManagedAddress,
+        payments: PaymentsVec<Self::Api>,
+    ) {
+        self.redelegate_validator_event(caller, from_validator, to_validator, payments);
+    }
+
+    #[inline]
+    fn emit_redelegate_sov_event(
+        &self,
+        caller: ManagedAddress,
+        from_sov: ManagedAddress,
+        to_sov: ManagedAddress,
+        payments: PaymentsVec<Self::Api>,
+    ) {
+        self.redelegate_sov_event(caller, from_sov, to_sov, payments);
+    }
+
+    #[inline]
+    fn emit_lock_shared_stake_event(
+        &self,
+        caller: ManagedAddress,
+        payments: PaymentsVec<Self::Api>,
+    ) {
+        self.lock_shared_stake_event(caller, payments);
+    }
+
+    #[inline]
+    fn emit_unlock_shared_stake_event(
+        &self,
+        caller: ManagedAddress,
+        payments: PaymentsVec<Self::Api>,
+    ) {
+        self.unlock_shared_stake_event(caller, payments);
+    }
+
+    #[inline]
+    fn emit_allocate_shared_stake_event(
+        &self,
+        caller: ManagedAddress,
+        sov: ManagedAddress,
+        payments: PaymentsVec<Self::Api>,
+    ) {
+        self.allocate_shared_stake_event(caller, sov, payments);
+    }
+
+    #[inline]
+    fn emit_deallocate_shared_stake_event(
+        &self,
+        caller: ManagedAddress,
+        sov: ManagedAddress,
+        payments: PaymentsVec<Self::Api>,
+    ) {
+        self.deallocate_shared_stake_event(caller, sov, payments);
+    }
+
+    #[inline]
+    fn emit_set_allocation_opt_in_event(
+        &self,
+        caller: ManagedAddress,
+        validator: ManagedAddress,
+        sov: ManagedAddress,
+        max_percent: Percent,
+    ) {
+        self.set_allocation_opt_in_event(caller, validator, sov, max_percent);
+    }
+
     #[inline]
     fn emit_unbond_tokens_caller_event(
         &self,
@@ -77,6 +151,49 @@ pub trait UserEventsModule {
         self.unbond_tokens_gravity_restaking_event(caller, payments);
     }
 
+    #[inline]
+    fn emit_tokenize_delegation_event(
+        &self,
+        caller: ManagedAddress,
+        target: ManagedAddress,
+        receipt_nonce: u64,
+        receipt_amount: BigUint,
+        payments: PaymentsVec<Self::Api>,
+    ) {
+        self.tokenize_delegation_event(caller, target, receipt_nonce, receipt_amount, payments);
+    }
+
+    #[inline]
+    fn emit_redeem_receipt_event(
+        &self,
+        caller: ManagedAddress,
+        target: ManagedAddress,
+        payments: PaymentsVec<Self::Api>,
+    ) {
+        self.redeem_receipt_event(caller, target, payments);
+    }
+
+    #[inline]
+    fn emit_set_auto_compound_event(&self, caller: ManagedAddress, enabled: bool) {
+        self.set_auto_compound_event(caller, enabled);
+    }
+
+    #[inline]
+    fn emit_claim_delegation_rewards_event(&self, delegation: ManagedAddress, rewards: BigUint) {
+        self.claim_delegation_rewards_event(delegation, rewards);
+    }
+
+    #[inline]
+    fn emit_collect_delegation_rewards_event(
+        &self,
+        caller: ManagedAddress,
+        delegation: ManagedAddress,
+        rewards: BigUint,
+        compounded: bool,
+    ) {
+        self.collect_delegation_rewards_event(caller, delegation, compounded, rewards);
+    }
+
     // Events
 
     #[event("userDepositEvent")]
@@ -126,6 +243,63 @@ pub trait UserEventsModule {
         payments: UniquePayments<Self::Api>,
     );
 
+    #[event("redelegateValidatorEvent")]
+    fn redelegate_validator_event(
+        &self,
+        #[indexed] caller: ManagedAddress,
+        #[indexed] from_validator: ManagedAddress,
+        #[indexed] to_validator: ManagedAddress,
+        payments: PaymentsVec<Self::Api>,
+    );
+
+    #[event("redelegateSovEvent")]
+    fn redelegate_sov_event(
+        &self,
+        #[indexed] caller: ManagedAddress,
+        #[indexed] from_sov: ManagedAddress,
+        #[indexed] to_sov: ManagedAddress,
+        payments: PaymentsVec<Self::Api>,
+    );
+
+    #[event("lockSharedStakeEvent")]
+    fn lock_shared_stake_event(
+        &self,
+        #[indexed] caller: ManagedAddress,
+        payments: PaymentsVec<Self::Api>,
+    );
+
+    #[event("unlockSharedStakeEvent")]
+    fn unlock_shared_stake_event(
+        &self,
+        #[indexed] caller: ManagedAddress,
+        payments: PaymentsVec<Self::Api>,
+    );
+
+    #[event("allocateSharedStakeEvent")]
+    fn allocate_shared_stake_event(
+        &self,
+        #[indexed] caller: ManagedAddress,
+        #[indexed] sov: ManagedAddress,
+        payments: PaymentsVec<Self::Api>,
+    );
+
+    #[event("deallocateSharedStakeEvent")]
+    fn deallocate_shared_stake_event(
+        &self,
+        #[indexed] caller: ManagedAddress,
+        #[indexed] sov: ManagedAddress,
+        payments: PaymentsVec<Self::Api>,
+    );
+
+    #[event("setAllocationOptInEvent")]
+    fn set_allocation_opt_in_event(
+        &self,
+        #[indexed] caller: ManagedAddress,
+        #[indexed] validator: ManagedAddress,
+        #[indexed] sov: ManagedAddress,
+        max_percent: Percent,
+    );
+
     #[event("unbondTokensCallerEvent")]
     fn unbond_tokens_caller_event(
         &self,
@@ -139,4 +313,41 @@ pub trait UserEventsModule {
         #[indexed] caller: ManagedAddress,
         payments: PaymentsVec<Self::Api>,
     );
+
+    #[event("tokenizeDelegationEvent")]
+    fn tokenize_delegation_event(
+        &self,
+        #[indexed] caller: ManagedAddress,
+        #[indexed] target: ManagedAddress,
+        #[indexed] receipt_nonce: u64,
+        receipt_amount: BigUint,
+        payments: PaymentsVec<Self::Api>,
+    );
+
+    #[event("redeemReceiptEvent")]
+    fn redeem_receipt_event(
+        &self,
+        #[indexed] caller: ManagedAddress,
+        #[indexed] target: ManagedAddress,
+        payments: PaymentsVec<Self::Api>,
+    );
+
+    #[event("setAutoCompoundEvent")]
+    fn set_auto_compound_event(&self, #[indexed] caller: ManagedAddress, enabled: bool);
+
+    #[event("claimDelegationRewardsEvent")]
+    fn claim_delegation_rewards_event(
+        &self,
+        #[indexed] delegation: ManagedAddress,
+        rewards: BigUint,
+    );
+
+    #[event("collectDelegationRewardsEvent")]
+    fn collect_delegation_rewards_event(
+        &self,
+        #[indexed] caller: ManagedAddress,
+        #[indexed] delegation: ManagedAddress,
+        #[indexed] compounded: bool,
+        rewards: BigUint,
+    );
 }
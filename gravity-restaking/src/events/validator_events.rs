@@ -1,6 +1,10 @@
 use crate::{
     unique_payments::PaymentsVec,
-    user_actions::validator::{BlsKey, Percent},
+    user_actions::{
+        sovereign::Epoch,
+        validator::{BlsKey, BlsKeyStatus, Percent},
+        validator_metadata::ValidatorMetadata,
+    },
 };
 
 multiversx_sc::imports!();
@@ -30,11 +34,52 @@ pub trait ValidatorEventsModule {
         self.validator_remove_bls_keys_event(validator, bls_keys);
     }
 
+    #[inline]
+    fn emit_validator_drop_legacy_bls_keys_event(
+        &self,
+        validator: ManagedAddress,
+        bls_keys: ManagedVec<BlsKey<Self::Api>>,
+    ) {
+        self.validator_drop_legacy_bls_keys_event(validator, bls_keys);
+    }
+
+    #[inline]
+    fn emit_validator_bls_key_status_event(
+        &self,
+        validator: ManagedAddress,
+        bls_key: BlsKey<Self::Api>,
+        old_status: BlsKeyStatus,
+        new_status: BlsKeyStatus,
+    ) {
+        self.validator_bls_key_status_event(validator, bls_key, old_status, new_status);
+    }
+
+    #[inline]
+    fn emit_validator_bls_key_sov_jail_event(
+        &self,
+        validator: ManagedAddress,
+        sov: ManagedAddress,
+        bls_key: BlsKey<Self::Api>,
+        jailed: bool,
+    ) {
+        self.validator_bls_key_sov_jail_event(validator, sov, bls_key, jailed);
+    }
+
     #[inline]
     fn emit_validator_set_fee_event(&self, validator: ManagedAddress, fee_percent: Percent) {
         self.validator_set_fee_event(validator, fee_percent);
     }
 
+    #[inline]
+    fn emit_validator_announce_fee_event(
+        &self,
+        validator: ManagedAddress,
+        fee_percent: Percent,
+        effective_epoch: Epoch,
+    ) {
+        self.validator_announce_fee_event(validator, fee_percent, effective_epoch);
+    }
+
     #[inline]
     fn emit_validator_set_max_delegation_event(
         &self,
@@ -53,6 +98,77 @@ pub trait ValidatorEventsModule {
         self.validator_add_own_delegation_event(validator, payments);
     }
 
+    #[inline]
+    fn emit_validator_revoke_own_delegation_event(
+        &self,
+        validator: ManagedAddress,
+        payments: PaymentsVec<Self::Api>,
+    ) {
+        self.validator_revoke_own_delegation_event(validator, payments);
+    }
+
+    #[inline]
+    fn emit_validator_allocate_to_sov_event(
+        &self,
+        validator: ManagedAddress,
+        sov: ManagedAddress,
+        amount: BigUint,
+    ) {
+        self.validator_allocate_to_sov_event(validator, sov, amount);
+    }
+
+    #[inline]
+    fn emit_validator_allocation_clamped_event(
+        &self,
+        validator: ManagedAddress,
+        sov: ManagedAddress,
+        amount: BigUint,
+    ) {
+        self.validator_allocation_clamped_event(validator, sov, amount);
+    }
+
+    #[inline]
+    fn emit_validator_set_metadata_event(
+        &self,
+        validator: ManagedAddress,
+        metadata: ValidatorMetadata<Self::Api>,
+    ) {
+        self.validator_set_metadata_event(validator, metadata);
+    }
+
+    #[inline]
+    fn emit_validator_change_name_event(&self, validator: ManagedAddress, new_name: ManagedBuffer) {
+        self.validator_change_name_event(validator, new_name);
+    }
+
+    #[inline]
+    fn emit_validator_propose_owner_event(
+        &self,
+        validator: ManagedAddress,
+        new_owner: ManagedAddress,
+    ) {
+        self.validator_propose_owner_event(validator, new_owner);
+    }
+
+    #[inline]
+    fn emit_validator_transfer_ownership_event(
+        &self,
+        old_owner: ManagedAddress,
+        new_owner: ManagedAddress,
+    ) {
+        self.validator_transfer_ownership_event(old_owner, new_owner);
+    }
+
+    #[inline]
+    fn emit_validator_start_unregister_event(&self, validator: ManagedAddress) {
+        self.validator_start_unregister_event(validator);
+    }
+
+    #[inline]
+    fn emit_validator_unregister_event(&self, validator: ManagedAddress) {
+        self.validator_unregister_event(validator);
+    }
+
     // Events
 
     #[event("validatorRegisterEvent")]
@@ -72,9 +188,42 @@ pub trait ValidatorEventsModule {
         bls_keys: ManagedVec<BlsKey<Self::Api>>,
     );
 
+    #[event("validatorDropLegacyBlsKeysEvent")]
+    fn validator_drop_legacy_bls_keys_event(
+        &self,
+        #[indexed] validator: ManagedAddress,
+        bls_keys: ManagedVec<BlsKey<Self::Api>>,
+    );
+
+    #[event("validatorBlsKeyStatusEvent")]
+    fn validator_bls_key_status_event(
+        &self,
+        #[indexed] validator: ManagedAddress,
+        #[indexed] bls_key: BlsKey<Self::Api>,
+        old_status: BlsKeyStatus,
+        new_status: BlsKeyStatus,
+    );
+
+    #[event("validatorBlsKeySovJailEvent")]
+    fn validator_bls_key_sov_jail_event(
+        &self,
+        #[indexed] validator: ManagedAddress,
+        #[indexed] sov: ManagedAddress,
+        #[indexed] bls_key: BlsKey<Self::Api>,
+        jailed: bool,
+    );
+
     #[event("validatorSetFeeEvent")]
     fn validator_set_fee_event(&self, #[indexed] validator: ManagedAddress, fee_percent: Percent);
 
+    #[event("validatorAnnounceFeeEvent")]
+    fn validator_announce_fee_event(
+        &self,
+        #[indexed] validator: ManagedAddress,
+        fee_percent: Percent,
+        effective_epoch: Epoch,
+    );
+
     #[event("validatorSetMaxDelegationEvent")]
     fn validator_set_max_delegation_event(
         &self,
@@ -88,4 +237,61 @@ pub trait ValidatorEventsModule {
         #[indexed] validator: ManagedAddress,
         payments: PaymentsVec<Self::Api>,
     );
+
+    #[event("validatorRevokeOwnDelegationEvent")]
+    fn validator_revoke_own_delegation_event(
+        &self,
+        #[indexed] validator: ManagedAddress,
+        payments: PaymentsVec<Self::Api>,
+    );
+
+    #[event("validatorAllocateToSovEvent")]
+    fn validator_allocate_to_sov_event(
+        &self,
+        #[indexed] validator: ManagedAddress,
+        #[indexed] sov: ManagedAddress,
+        amount: BigUint,
+    );
+
+    #[event("validatorAllocationClampedEvent")]
+    fn validator_allocation_clamped_event(
+        &self,
+        #[indexed] validator: ManagedAddress,
+        #[indexed] sov: ManagedAddress,
+        amount: BigUint,
+    );
+
+    #[event("validatorSetMetadataEvent")]
+    fn validator_set_metadata_event(
+        &self,
+        #[indexed] validator: ManagedAddress,
+        metadata: ValidatorMetadata<Self::Api>,
+    );
+
+    #[event("validatorChangeNameEvent")]
+    fn validator_change_name_event(
+        &self,
+        #[indexed] validator: ManagedAddress,
+        new_name: ManagedBuffer,
+    );
+
+    #[event("validatorProposeOwnerEvent")]
+    fn validator_propose_owner_event(
+        &self,
+        #[indexed] validator: ManagedAddress,
+        #[indexed] new_owner: ManagedAddress,
+    );
+
+    #[event("validatorTransferOwnershipEvent")]
+    fn validator_transfer_ownership_event(
+        &self,
+        #[indexed] old_owner: ManagedAddress,
+        #[indexed] new_owner: ManagedAddress,
+    );
+
+    #[event("validatorStartUnregisterEvent")]
+    fn validator_start_unregister_event(&self, #[indexed] validator: ManagedAddress);
+
+    #[event("validatorUnregisterEvent")]
+    fn validator_unregister_event(&self, #[indexed] validator: ManagedAddress);
 }
@@ -6,22 +6,42 @@ multiversx_sc::imports!();
 
 pub mod events;
 pub mod token_whitelist;
+pub mod treasury;
 pub mod unique_payments;
 pub mod user_actions;
+pub mod views;
 
 #[multiversx_sc::contract]
 pub trait GravityRestaking:
     user_actions::call_delegation::CallDelegationModule
     + token_whitelist::TokenWhitelistModule
+    + treasury::TreasuryModule
     + user_actions::user::UserModule
+    + user_actions::redelegate::RedelegateModule
+    + user_actions::shared_security::SharedSecurityModule
+    + user_actions::operator_allocation::OperatorAllocationModule
+    + user_actions::allocation_capacity::AllocationCapacityModule
+    + user_actions::ownership_transfer::OwnershipTransferModule
+    + user_actions::receipt_token::ReceiptTokenModule
+    + user_actions::security_snapshot::SecuritySnapshotModule
+    + user_actions::deregistration::DeregistrationModule
     + user_actions::validator::ValidatorModule
+    + user_actions::self_bond::SelfBondModule
+    + user_actions::own_delegation::OwnDelegationModule
+    + user_actions::validator_metadata::ValidatorMetadataModule
+    + user_actions::bls_key_status::BlsKeyStatusModule
     + user_actions::sovereign::SovereignModule
+    + user_actions::sov_allow_list::SovAllowListModule
     + user_actions::common_actions::CommonActionsModule
     + user_actions::common_storage::CommonStorageModule
+    + user_actions::delegation_rewards::DelegationRewardsModule
     + user_actions::unbond::UnbondModule
+    + user_actions::user_targets::UserTargetsModule
+    + views::ViewsModule
     + events::user_events::UserEventsModule
     + events::validator_events::ValidatorEventsModule
     + events::sov_events::SovEventsModule
+    + events::treasury_events::TreasuryEventsModule
     + utils::UtilsModule
 {
     #[init]
@@ -0,0 +1,146 @@
+use crate::{
+    unique_payments::UniquePayments,
+    user_actions::{
+        call_delegation::EGLD_TOKEN_ID,
+        validator::{Percent, MAX_PERCENT},
+    },
+};
+
+multiversx_sc::imports!();
+
+/// 50%
+pub const MAX_PROTOCOL_FEE: Percent = 5_000;
+
+/// Protocol fee, taken from the rewards flowing through the contract.
+/// Fees accrue per token until claimed by the treasury.
+#[multiversx_sc::module]
+pub trait TreasuryModule: crate::events::treasury_events::TreasuryEventsModule {
+    #[only_owner]
+    #[endpoint(setProtocolFee)]
+    fn set_protocol_fee(&self, protocol_fee: Percent) {
+        require!(protocol_fee <= MAX_PROTOCOL_FEE, "Invalid protocol fee");
+
+        self.protocol_fee().set(protocol_fee);
+
+        self.emit_set_protocol_fee_event(protocol_fee);
+    }
+
+    #[only_owner]
+    #[endpoint(setTreasury)]
+    fn set_treasury(&self, treasury: ManagedAddress) {
+        require!(!treasury.is_zero(), "Invalid treasury address");
+
+        self.treasury().set(&treasury);
+
+        self.emit_set_treasury_event(treasury);
+    }
+
+    #[endpoint(claimProtocolFees)]
+    fn claim_protocol_fees(&self) {
+        let caller = self.blockchain().get_caller();
+        require!(
+            !self.treasury().is_empty() && caller == self.treasury().get(),
+            "Only the treasury may claim protocol fees"
+        );
+
+        let accrued_fees_mapper = self.accrued_protocol_fees();
+        require!(!accrued_fees_mapper.is_empty(), "No protocol fees to claim");
+
+        let mut output_payments = accrued_fees_mapper.take().into_payments();
+        require!(!output_payments.is_empty(), "No protocol fees to claim");
+
+        let claimed_payments = output_payments.clone();
+        let egld_token_id = TokenIdentifier::from_esdt_bytes(EGLD_TOKEN_ID);
+        let mut opt_index_to_remove = None;
+        for (i, payment) in output_payments.iter().enumerate() {
+            if payment.token_identifier == egld_token_id {
+                opt_index_to_remove = Some(i);
+
+                break;
+            }
+        }
+
+        if let Some(index_to_remove) = opt_index_to_remove {
+            let egld_payment = output_payments.get(index_to_remove);
+            output_payments.remove(index_to_remove);
+
+            self.send().direct_egld(&caller, &egld_payment.amount);
+        }
+
+        if !output_payments.is_empty() {
+            self.send().direct_multi(&caller, &output_payments);
+        }
+
+        self.emit_claim_protocol_fees_event(caller, claimed_payments);
+    }
+
+    #[view(getAccruedProtocolFees)]
+    fn get_accrued_protocol_fees(&self) -> UniquePayments<Self::Api> {
+        let accrued_fees_mapper = self.accrued_protocol_fees();
+        if accrued_fees_mapper.is_empty() {
+            return UniquePayments::new();
+        }
+
+        accrued_fees_mapper.get()
+    }
+
+    #[view(getTreasury)]
+    fn get_treasury(&self) -> OptionalValue<ManagedAddress> {
+        if self.treasury().is_empty() {
+            return OptionalValue::None;
+        }
+
+        OptionalValue::Some(self.treasury().get())
+    }
+
+    /// Returns the rewards left after the protocol fee
+    fn take_protocol_fee(&self, rewards: EsdtTokenPayment) -> EsdtTokenPayment {
+        let protocol_fee = self.protocol_fee().get();
+        let fee_amount = rewards.amount.clone() * protocol_fee / MAX_PERCENT;
+        if fee_amount == 0 {
+            return rewards;
+        }
+
+        self.accrue_protocol_fee(EsdtTokenPayment::new(
+            rewards.token_identifier.clone(),
+            rewards.token_nonce,
+            fee_amount.clone(),
+        ));
+
+        EsdtTokenPayment::new(
+            rewards.token_identifier,
+            rewards.token_nonce,
+            rewards.amount - fee_amount,
+        )
+    }
+
+    fn take_protocol_fee_egld(&self, rewards: BigUint) -> BigUint {
+        let egld_rewards =
+            EsdtTokenPayment::new(TokenIdentifier::from_esdt_bytes(EGLD_TOKEN_ID), 0, rewards);
+
+        self.take_protocol_fee(egld_rewards).amount
+    }
+
+    fn accrue_protocol_fee(&self, fee_payment: EsdtTokenPayment) {
+        let accrued_fees_mapper = self.accrued_protocol_fees();
+        let mut accrued_fees = if accrued_fees_mapper.is_empty() {
+            UniquePayments::new()
+        } else {
+            accrued_fees_mapper.get()
+        };
+        accrued_fees.add_payment(fee_payment.clone());
+        accrued_fees_mapper.set(accrued_fees);
+
+        self.emit_protocol_fee_accrued_event(fee_payment);
+    }
+
+    #[view(getProtocolFee)]
+    #[storage_mapper("protocolFee")]
+    fn protocol_fee(&self) -> SingleValueMapper<Percent>;
+
+    #[storage_mapper("treasury")]
+    fn treasury(&self) -> SingleValueMapper<ManagedAddress>;
+
+    #[storage_mapper("accruedProtocolFees")]
+    fn accrued_protocol_fees(&self) -> SingleValueMapper<UniquePayments<Self::Api>>;
+}
@@ -102,6 +102,21 @@ impl<M: ManagedTypeApi> UniquePayments<M> {
         Result::Err(())
     }
 
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.payments.is_empty()
+    }
+
+    pub fn get_amount(&self, token_id: &TokenIdentifier<M>, nonce: u64) -> BigUint<M> {
+        for payment in &self.payments {
+            if &payment.token_identifier == token_id && payment.token_nonce == nonce {
+                return payment.amount;
+            }
+        }
+
+        BigUint::zero()
+    }
+
     #[inline]
     pub fn into_payments(self) -> PaymentsVec<M> {
         self.payments
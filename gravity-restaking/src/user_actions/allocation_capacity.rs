@@ -0,0 +1,126 @@
+use crate::unique_payments::UniquePayments;
+
+use super::validator::{Percent, MAX_PERCENT};
+
+multiversx_sc::imports!();
+
+/// Running aggregate of the stake the delegators of a validator opted in for allocation to a sovereign.
+/// Only the tokens accepted by the sovereign count.
+/// Kept in sync on every change of a delegator's stake or opt-in,
+/// or of the tokens accepted by the sovereign.
+/// Operator allocations above the capacity are clamped down to it.
+#[multiversx_sc::module]
+pub trait AllocationCapacityModule:
+    crate::token_whitelist::TokenWhitelistModule
+    + super::sovereign::SovereignModule
+    + super::common_actions::CommonActionsModule
+    + super::common_storage::CommonStorageModule
+    + crate::events::sov_events::SovEventsModule
+    + utils::UtilsModule
+{
+    /// Returns the updated capacity, which the operator allocation may no longer exceed
+    fn sync_capacity_contribution(
+        &self,
+        user_id: AddressId,
+        validator_id: AddressId,
+        sov_id: AddressId,
+        delegated_tokens: &UniquePayments<Self::Api>,
+    ) -> BigUint {
+        let opt_in = self.allocation_opt_in(user_id, validator_id, sov_id).get();
+        let contribution = if opt_in == 0 {
+            BigUint::zero()
+        } else {
+            self.get_sov_accepted_value(sov_id, delegated_tokens) * opt_in / MAX_PERCENT
+        };
+
+        let contribution_mapper = self.capacity_contribution(user_id, validator_id, sov_id);
+        let previous_contribution = contribution_mapper.get();
+        if contribution == previous_contribution {
+            return self.allocation_capacity(validator_id, sov_id).get();
+        }
+
+        let capacity = self
+            .allocation_capacity(validator_id, sov_id)
+            .update(|capacity| {
+                *capacity -= &previous_contribution;
+                *capacity += &contribution;
+
+                capacity.clone()
+            });
+        if contribution == 0 {
+            contribution_mapper.clear();
+        } else {
+            contribution_mapper.set(contribution);
+        }
+
+        capacity
+    }
+
+    fn get_sov_accepted_value(
+        &self,
+        sov_id: AddressId,
+        delegated_tokens: &UniquePayments<Self::Api>,
+    ) -> BigUint {
+        let accepted_tokens_mapper = self.sov_accepted_tokens(sov_id);
+        let mut total = BigUint::zero();
+        for payment in &delegated_tokens.clone().into_payments() {
+            if accepted_tokens_mapper.is_empty()
+                || accepted_tokens_mapper.contains(&payment.token_identifier)
+            {
+                total += self.get_total_staked_egld(&payment.token_identifier, &payment.amount);
+            }
+        }
+
+        total
+    }
+
+    #[storage_mapper("allocationOptIn")]
+    fn allocation_opt_in(
+        &self,
+        user_id: AddressId,
+        validator_id: AddressId,
+        sov_id: AddressId,
+    ) -> SingleValueMapper<Percent>;
+
+    /// Pairs of (user_id, validator_id) opted in for allocation to the sovereign
+    #[storage_mapper("sovAllocationOptIns")]
+    fn sov_allocation_opt_ins(
+        &self,
+        sov_id: AddressId,
+    ) -> UnorderedSetMapper<(AddressId, AddressId)>;
+
+    #[storage_mapper("optedInSovs")]
+    fn opted_in_sovs(
+        &self,
+        user_id: AddressId,
+        validator_id: AddressId,
+    ) -> UnorderedSetMapper<AddressId>;
+
+    #[storage_mapper("capacityContribution")]
+    fn capacity_contribution(
+        &self,
+        user_id: AddressId,
+        validator_id: AddressId,
+        sov_id: AddressId,
+    ) -> SingleValueMapper<BigUint>;
+
+    #[storage_mapper("allocationCapacity")]
+    fn allocation_capacity(
+        &self,
+        validator_id: AddressId,
+        sov_id: AddressId,
+    ) -> SingleValueMapper<BigUint>;
+
+    #[storage_mapper("operatorAllocation")]
+    fn operator_allocation(
+        &self,
+        validator_id: AddressId,
+        sov_id: AddressId,
+    ) -> SingleValueMapper<BigUint>;
+
+    #[storage_mapper("operatorSovs")]
+    fn operator_sovs(&self, validator_id: AddressId) -> UnorderedSetMapper<AddressId>;
+
+    #[storage_mapper("allSovOperators")]
+    fn all_sov_operators(&self, sov_id: AddressId) -> UnorderedSetMapper<AddressId>;
+}
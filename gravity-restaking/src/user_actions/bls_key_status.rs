@@ -0,0 +1,229 @@
+use super::validator::{BlsKey, BlsKeyStatus};
+
+multiversx_sc::imports!();
+
+/// Keys start as Pending when added.
+/// Sovereign chains the validator allocates to (or the owner) activate and jail keys,
+/// only the owner may unjail them, and the validator may unstake and restake its own keys.
+/// A sovereign chain only jails keys out of its own security set, the owner jails them globally.
+#[multiversx_sc::module]
+pub trait BlsKeyStatusModule:
+    crate::token_whitelist::TokenWhitelistModule
+    + super::validator::ValidatorModule
+    + super::sovereign::SovereignModule
+    + super::operator_allocation::OperatorAllocationModule
+    + super::common_actions::CommonActionsModule
+    + super::common_storage::CommonStorageModule
+    + crate::events::user_events::UserEventsModule
+    + crate::events::validator_events::ValidatorEventsModule
+    + utils::UtilsModule
+{
+    #[endpoint(unstakeKeys)]
+    fn unstake_keys(&self, keys: MultiValueEncoded<BlsKey<Self::Api>>) {
+        let caller = self.blockchain().get_caller();
+        let caller_id = self.resolve_validator_id_non_zero(&caller);
+        for key in keys {
+            self.require_key_of_validator(&key, caller_id);
+            self.change_key_status(
+                &caller,
+                key,
+                &[BlsKeyStatus::Pending, BlsKeyStatus::Active],
+                BlsKeyStatus::Unstaked,
+            );
+        }
+    }
+
+    #[endpoint(restakeKeys)]
+    fn restake_keys(&self, keys: MultiValueEncoded<BlsKey<Self::Api>>) {
+        let caller = self.blockchain().get_caller();
+        let caller_id = self.resolve_validator_id_non_zero(&caller);
+        for key in keys {
+            self.require_key_of_validator(&key, caller_id);
+            self.change_key_status(
+                &caller,
+                key,
+                &[BlsKeyStatus::Unstaked],
+                BlsKeyStatus::Pending,
+            );
+        }
+    }
+
+    #[endpoint(activateKeys)]
+    fn activate_keys(&self, keys: MultiValueEncoded<BlsKey<Self::Api>>) {
+        let caller = self.blockchain().get_caller();
+        for key in keys {
+            let validator = self.require_key_manager(&key, &caller);
+            self.change_key_status(
+                &validator,
+                key,
+                &[BlsKeyStatus::Pending],
+                BlsKeyStatus::Active,
+            );
+        }
+    }
+
+    #[endpoint(jailKeys)]
+    fn jail_keys(&self, keys: MultiValueEncoded<BlsKey<Self::Api>>) {
+        let caller = self.blockchain().get_caller();
+        let is_owner = caller == self.blockchain().get_owner_address();
+        for key in keys {
+            let validator = self.require_key_manager(&key, &caller);
+            if is_owner {
+                self.change_key_status(
+                    &validator,
+                    key,
+                    &[BlsKeyStatus::Pending, BlsKeyStatus::Active],
+                    BlsKeyStatus::Jailed,
+                );
+                continue;
+            }
+
+            require!(
+                self.bls_key_status(&key).get() == BlsKeyStatus::Active,
+                "Key not active"
+            );
+
+            let sov_id = self.resolve_sov_id(&caller);
+            let newly_jailed = self.bls_key_jailed_for_sov(&key).insert(sov_id);
+            require!(newly_jailed, "Key already jailed");
+
+            self.emit_validator_bls_key_sov_jail_event(validator, caller.clone(), key, true);
+        }
+    }
+
+    /// Unjailed keys have to be activated again
+    #[only_owner]
+    #[endpoint(unjailKeys)]
+    fn unjail_keys(&self, keys: MultiValueEncoded<BlsKey<Self::Api>>) {
+        for key in keys {
+            let validator_id = self.require_known_key(&key);
+            let validator = unsafe { self.get_validator_owner(validator_id).unwrap_unchecked() };
+            self.change_key_status(
+                &validator,
+                key,
+                &[BlsKeyStatus::Jailed],
+                BlsKeyStatus::Pending,
+            );
+        }
+    }
+
+    #[only_owner]
+    #[endpoint(unjailKeysForSov)]
+    fn unjail_keys_for_sov(
+        &self,
+        sov_address: ManagedAddress,
+        keys: MultiValueEncoded<BlsKey<Self::Api>>,
+    ) {
+        let sov_id = self.resolve_sov_id_non_zero(&sov_address);
+        for key in keys {
+            let validator_id = self.require_known_key(&key);
+            let was_jailed = self.bls_key_jailed_for_sov(&key).swap_remove(&sov_id);
+            require!(was_jailed, "Key not jailed");
+
+            let validator = unsafe { self.get_validator_owner(validator_id).unwrap_unchecked() };
+            self.emit_validator_bls_key_sov_jail_event(validator, sov_address.clone(), key, false);
+        }
+    }
+
+    #[view(isBlsKeyJailedForSov)]
+    fn is_bls_key_jailed_for_sov(
+        &self,
+        bls_key: BlsKey<Self::Api>,
+        sov_address: ManagedAddress,
+    ) -> bool {
+        let sov_id = self.resolve_sov_id(&sov_address);
+        if sov_id == NULL_ID {
+            return false;
+        }
+
+        self.bls_key_jailed_for_sov(&bls_key).contains(&sov_id)
+    }
+
+    #[view(getBlsKeyStatus)]
+    fn get_bls_key_status(&self, bls_key: BlsKey<Self::Api>) -> OptionalValue<BlsKeyStatus> {
+        let key_status_mapper = self.bls_key_status(&bls_key);
+        if key_status_mapper.is_empty() {
+            return OptionalValue::None;
+        }
+
+        OptionalValue::Some(key_status_mapper.get())
+    }
+
+    #[view(getBlsKeysByStatus)]
+    fn get_bls_keys_by_status(
+        &self,
+        validator: ManagedAddress,
+        status: BlsKeyStatus,
+    ) -> MultiValueEncoded<BlsKey<Self::Api>> {
+        let mut result = MultiValueEncoded::new();
+        let validator_id = self.resolve_validator_id(&validator);
+        if validator_id == NULL_ID {
+            return result;
+        }
+
+        for bls_key in self.bls_keys(validator_id).iter() {
+            if self.bls_key_status(&bls_key).get() == status {
+                result.push(bls_key);
+            }
+        }
+
+        result
+    }
+
+    fn change_key_status(
+        &self,
+        validator: &ManagedAddress,
+        bls_key: BlsKey<Self::Api>,
+        allowed_from: &[BlsKeyStatus],
+        new_status: BlsKeyStatus,
+    ) {
+        let key_status_mapper = self.bls_key_status(&bls_key);
+        let old_status = key_status_mapper.get();
+        require!(
+            allowed_from.contains(&old_status),
+            "Invalid key status transition"
+        );
+
+        key_status_mapper.set(new_status);
+
+        self.emit_validator_bls_key_status_event(
+            validator.clone(),
+            bls_key,
+            old_status,
+            new_status,
+        );
+    }
+
+    fn require_known_key(&self, bls_key: &BlsKey<Self::Api>) -> AddressId {
+        let key_owner_mapper = self.bls_key_owner(bls_key);
+        require!(!key_owner_mapper.is_empty(), "Key not found");
+
+        key_owner_mapper.get()
+    }
+
+    fn require_key_of_validator(&self, bls_key: &BlsKey<Self::Api>, validator_id: AddressId) {
+        require!(
+            self.require_known_key(bls_key) == validator_id,
+            "Key not found"
+        );
+    }
+
+    /// The owner, or a sovereign chain the key's validator allocated stake to.
+    /// Returns the address of the key's validator.
+    fn require_key_manager(
+        &self,
+        bls_key: &BlsKey<Self::Api>,
+        caller: &ManagedAddress,
+    ) -> ManagedAddress {
+        let validator_id = self.require_known_key(bls_key);
+        if caller != &self.blockchain().get_owner_address() {
+            let sov_id = self.resolve_sov_id(caller);
+            require!(
+                sov_id != NULL_ID && self.all_sov_operators(sov_id).contains(&validator_id),
+                "Not allowed to manage this key"
+            );
+        }
+
+        unsafe { self.get_validator_owner(validator_id).unwrap_unchecked() }
+    }
+}
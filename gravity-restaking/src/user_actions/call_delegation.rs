@@ -9,6 +9,13 @@ mod delegation_proxy {
     pub trait DelegationProxy {
         #[endpoint(moveStakeToReStaking)]
         fn move_stake_to_re_staking(&self, user: ManagedAddress, value: BigUint);
+
+        #[payable("EGLD")]
+        #[endpoint(delegate)]
+        fn delegate(&self);
+
+        #[endpoint(claimRewards)]
+        fn claim_rewards(&self);
     }
 }
 
@@ -28,9 +35,12 @@ pub trait CallDelegationModule:
     + super::unbond::UnbondModule
     + super::common_actions::CommonActionsModule
     + super::common_storage::CommonStorageModule
+    + super::delegation_rewards::DelegationRewardsModule
+    + crate::treasury::TreasuryModule
     + crate::events::user_events::UserEventsModule
     + crate::events::validator_events::ValidatorEventsModule
     + crate::events::sov_events::SovEventsModule
+    + crate::events::treasury_events::TreasuryEventsModule
     + utils::UtilsModule
 {
     #[endpoint(moveStakeToReStaking)]
@@ -45,6 +55,51 @@ pub trait CallDelegationModule:
         self.call_restake_async(delegation, caller, value);
     }
 
+    /// Claims the rewards of the stake moved in from the given delegation contract.
+    /// Can be called by anyone, e.g. a keeper bot.
+    #[endpoint(claimDelegationRewards)]
+    fn claim_delegation_rewards(&self, delegation: ManagedAddress) {
+        require!(
+            self.delegation_contracts().contains(&delegation),
+            "Unknown delegation contract"
+        );
+
+        self.delegation_proxy_obj(delegation.clone())
+            .claim_rewards()
+            .async_call_promise()
+            .with_callback(
+                <Self as CallDelegationModule>::callbacks(self).claim_rewards_callback(delegation),
+            )
+            .register_promise();
+    }
+
+    /// Credits the caller's share of the claimed rewards as EGLD,
+    /// or re-delegates it to the delegation contract if auto-compound is enabled
+    #[endpoint(collectDelegationRewards)]
+    fn collect_delegation_rewards(&self, delegation: ManagedAddress) {
+        let caller = self.blockchain().get_caller();
+        let caller_id = self.user_ids().get_id_non_zero(&caller);
+        let rewards = self.take_delegation_rewards(&delegation, caller_id);
+        require!(rewards > 0, "No rewards to collect");
+
+        if !self.auto_compound(caller_id).get() {
+            self.add_user_egld(&caller, &rewards);
+            self.emit_collect_delegation_rewards_event(caller, delegation, rewards, false);
+
+            return;
+        }
+
+        self.delegation_proxy_obj(delegation.clone())
+            .delegate()
+            .with_egld_transfer(rewards.clone())
+            .async_call_promise()
+            .with_callback(
+                <Self as CallDelegationModule>::callbacks(self)
+                    .compound_rewards_callback(caller, delegation, rewards),
+            )
+            .register_promise();
+    }
+
     fn call_restake_async(&self, delegation: ManagedAddress, user: ManagedAddress, value: BigUint) {
         self.delegation_proxy_obj(delegation)
             .move_stake_to_re_staking(user.clone(), value.clone())
@@ -66,26 +121,92 @@ pub trait CallDelegationModule:
             return;
         }
 
+        let caller_id = self.add_user_egld(&original_caller, &original_value);
+
+        let delegation = self.blockchain().get_caller();
+        self.add_moved_stake(&delegation, caller_id, &original_value);
+
+        self.emit_move_stake_event(original_caller, delegation, original_value);
+    }
+
+    #[callback]
+    fn claim_rewards_callback(
+        &self,
+        delegation: ManagedAddress,
+        #[call_result] call_result: ManagedAsyncCallResult<()>,
+    ) {
+        if call_result.is_err() {
+            return;
+        }
+
+        // the share of the stake no user earns on can't be attributed and goes to the protocol
+        let rewards = self.call_value().egld_value().clone_value();
+        if rewards > 0 {
+            let user_rewards = self.take_protocol_fee_egld(rewards);
+            let attributed_rewards = self.distribute_delegation_rewards(&delegation, &user_rewards);
+            let unattributed_rewards = &user_rewards - &attributed_rewards;
+            if unattributed_rewards > 0 {
+                self.accrue_protocol_fee(EsdtTokenPayment::new(
+                    TokenIdentifier::from_esdt_bytes(EGLD_TOKEN_ID),
+                    0,
+                    unattributed_rewards,
+                ));
+            }
+
+            if attributed_rewards > 0 {
+                self.emit_claim_delegation_rewards_event(delegation.clone(), attributed_rewards);
+            }
+        }
+
+        self.activate_pending_moved_stake(&delegation);
+    }
+
+    /// The re-delegated rewards only count as moved stake. On failure, they're credited as EGLD instead.
+    #[callback]
+    fn compound_rewards_callback(
+        &self,
+        original_caller: ManagedAddress,
+        delegation: ManagedAddress,
+        rewards: BigUint,
+        #[call_result] call_result: ManagedAsyncCallResult<()>,
+    ) {
+        let compounded = call_result.is_ok();
+        if compounded {
+            let caller_id = self.user_ids().get_id_non_zero(&original_caller);
+            self.add_moved_stake(&delegation, caller_id, &rewards);
+        } else {
+            self.add_user_egld(&original_caller, &rewards);
+        }
+
+        self.emit_collect_delegation_rewards_event(
+            original_caller,
+            delegation,
+            rewards,
+            compounded,
+        );
+    }
+
+    fn add_user_egld(&self, user: &ManagedAddress, amount: &BigUint) -> AddressId {
         let ids_mapper = self.user_ids();
-        let mut caller_id = ids_mapper.get_id(&original_caller);
-        let mut user_tokens = if caller_id == NULL_ID {
-            caller_id = ids_mapper.insert_new(&original_caller);
+        let mut user_id = ids_mapper.get_id(user);
+        let mut user_tokens = if user_id == NULL_ID {
+            user_id = ids_mapper.insert_new(user);
 
             UniquePayments::new()
         } else {
-            self.user_tokens(caller_id).get()
+            self.user_tokens(user_id).get()
         };
 
         let egld_payment = EsdtTokenPayment::new(
             TokenIdentifier::from_esdt_bytes(EGLD_TOKEN_ID),
             0,
-            original_value.clone(),
+            amount.clone(),
         );
-        user_tokens.add_payment(egld_payment);
-        self.user_tokens(caller_id).set(user_tokens);
+        user_tokens.add_payment(egld_payment.clone());
+        self.user_tokens(user_id).set(user_tokens);
+        self.add_total_deposited(&ManagedVec::from_single_item(egld_payment));
 
-        let delegation = self.blockchain().get_caller();
-        self.emit_move_stake_event(original_caller, delegation, original_value);
+        user_id
     }
 
     #[proxy]
@@ -2,32 +2,51 @@ use multiversx_sc::api::StorageMapperApi;
 
 use crate::unique_payments::{PaymentsVec, UniquePayments};
 
-use super::user::PaymentsMultiValue;
+use super::{sovereign::Epoch, user::PaymentsMultiValue};
 
 multiversx_sc::imports!();
+multiversx_sc::derive_imports!();
+
+/// Amount in effect from the given epoch on.
+/// Changes take effect from the epoch after the one they were made in.
+#[derive(TypeAbi, TopEncode, TopDecode, NestedEncode, NestedDecode)]
+pub struct StakeCheckpoint<M: ManagedTypeApi> {
+    pub epoch: Epoch,
+    pub amount: BigUint<M>,
+}
 
 pub struct AddDelegationArgs<'a, S: StorageMapperApi> {
     pub total_delegated_mapper: SingleValueMapper<S, BigUint<S>>,
     pub total_by_user_mapper: SingleValueMapper<S, BigUint<S>>,
+    pub target_checkpoints_mapper: VecMapper<S, StakeCheckpoint<S>>,
+    pub user_checkpoints_mapper: VecMapper<S, StakeCheckpoint<S>>,
     pub all_delegators_mapper: &'a mut UnorderedSetMapper<S, AddressId>,
+    pub user_targets_mapper: &'a mut UnorderedSetMapper<S, AddressId>,
     pub delegated_by_mapper: SingleValueMapper<S, UniquePayments<S>>,
     pub opt_max_delegation: Option<BigUint<S>>,
     pub payments_to_add: PaymentsVec<S>,
     pub total_amount: BigUint<S>,
+    pub target_id: AddressId,
     pub caller_id: AddressId,
 }
 
 pub struct RemoveDelegationArgs<'a, S: StorageMapperApi> {
     pub total_delegated_mapper: SingleValueMapper<S, BigUint<S>>,
     pub total_by_user_mapper: SingleValueMapper<S, BigUint<S>>,
+    pub target_checkpoints_mapper: VecMapper<S, StakeCheckpoint<S>>,
+    pub user_checkpoints_mapper: VecMapper<S, StakeCheckpoint<S>>,
     pub all_delegators_mapper: &'a mut UnorderedSetMapper<S, AddressId>,
+    pub user_targets_mapper: &'a mut UnorderedSetMapper<S, AddressId>,
     pub delegated_by_mapper: SingleValueMapper<S, UniquePayments<S>>,
     pub tokens: PaymentsMultiValue<S>,
+    pub target_id: AddressId,
     pub caller_id: AddressId,
 }
 
 #[multiversx_sc::module]
-pub trait CommonActionsModule: crate::token_whitelist::TokenWhitelistModule {
+pub trait CommonActionsModule:
+    crate::token_whitelist::TokenWhitelistModule + super::common_storage::CommonStorageModule
+{
     fn before_add_delegation(
         &self,
         user_tokens_mapper: SingleValueMapper<UniquePayments<Self::Api>>,
@@ -55,19 +74,105 @@ pub trait CommonActionsModule: crate::token_whitelist::TokenWhitelistModule {
         (output_payments, total)
     }
 
+    fn get_whitelisted_payments_total(&self, payments: &PaymentsVec<Self::Api>) -> BigUint {
+        let mut total = BigUint::zero();
+        for payment in payments {
+            self.require_token_in_whitelist(&payment.token_identifier);
+
+            total += self.get_total_staked_egld(&payment.token_identifier, &payment.amount);
+        }
+
+        total
+    }
+
+    fn to_payments_multi_value(
+        &self,
+        payments: PaymentsVec<Self::Api>,
+    ) -> PaymentsMultiValue<Self::Api> {
+        let mut result = MultiValueEncoded::new();
+        for payment in &payments {
+            result.push(
+                (
+                    payment.token_identifier,
+                    payment.token_nonce,
+                    payment.amount,
+                )
+                    .into(),
+            );
+        }
+
+        result
+    }
+
+    fn get_payments_or_default(
+        &self,
+        mapper: &SingleValueMapper<UniquePayments<Self::Api>>,
+    ) -> UniquePayments<Self::Api> {
+        if mapper.is_empty() {
+            return UniquePayments::new();
+        }
+
+        mapper.get()
+    }
+
+    fn add_total_deposited(&self, payments: &PaymentsVec<Self::Api>) {
+        let total_deposited_mapper = self.total_deposited();
+        let mut total_deposited = self.get_payments_or_default(&total_deposited_mapper);
+        for payment in payments {
+            total_deposited.add_payment(payment);
+        }
+        total_deposited_mapper.set(total_deposited);
+    }
+
+    /// Funds deposited before the total was tracked may not be part of it
+    fn deduct_total_deposited(&self, payments: &PaymentsVec<Self::Api>) {
+        let total_deposited_mapper = self.total_deposited();
+        let mut total_deposited = self.get_payments_or_default(&total_deposited_mapper);
+        for payment in payments {
+            let deposited_amount =
+                total_deposited.get_amount(&payment.token_identifier, payment.token_nonce);
+            let amount = core::cmp::min(deposited_amount, payment.amount);
+            if amount == 0 {
+                continue;
+            }
+
+            let _ = total_deposited.deduct_payment(&EsdtTokenPayment::new(
+                payment.token_identifier,
+                payment.token_nonce,
+                amount,
+            ));
+        }
+        total_deposited_mapper.set(total_deposited);
+    }
+
     fn add_delegation(&self, args: AddDelegationArgs<Self::Api>) {
-        args.total_delegated_mapper.update(|total_del| {
+        let new_total = args.total_delegated_mapper.update(|total_del| {
             *total_del += &args.total_amount;
 
             if let Some(max_amt) = args.opt_max_delegation {
                 require!(*total_del <= max_amt, "Max delegated amount exceeded");
             }
+
+            total_del.clone()
         });
+        let new_user_total = args.total_by_user_mapper.update(|total_user| {
+            *total_user += &args.total_amount;
 
-        args.total_by_user_mapper
-            .update(|total_user| *total_user += args.total_amount);
+            total_user.clone()
+        });
+        self.record_stake_checkpoint(
+            &args.target_checkpoints_mapper,
+            &(&new_total - &args.total_amount),
+            new_total,
+        );
+        self.record_stake_checkpoint(
+            &args.user_checkpoints_mapper,
+            &(&new_user_total - &args.total_amount),
+            new_user_total,
+        );
 
         let _ = args.all_delegators_mapper.insert(args.caller_id);
+        let _ = args.user_targets_mapper.insert(args.target_id);
 
         let mut tokens_delegated_by_user = if !args.delegated_by_mapper.is_empty() {
             args.delegated_by_mapper.get()
@@ -88,7 +193,7 @@ pub trait CommonActionsModule: crate::token_whitelist::TokenWhitelistModule {
 
         let mut output_payments = PaymentsVec::new();
         let mut total = BigUint::zero();
-        args.delegated_by_mapper.update(|delegated_tokens| {
+        let removed_all = args.delegated_by_mapper.update(|delegated_tokens| {
             for token_tuple in args.tokens {
                 let (token_id, nonce, amount) = token_tuple.into_tuple();
                 require!(amount > 0, "Can't revoke 0");
@@ -100,19 +205,99 @@ pub trait CommonActionsModule: crate::token_whitelist::TokenWhitelistModule {
                 total += self.get_total_staked_egld(&payment.token_identifier, &payment.amount);
                 output_payments.push(payment);
             }
+
+            delegated_tokens.is_empty()
         });
 
-        args.total_delegated_mapper.update(|total_del| {
+        // token values might have changed since delegating, so remove exactly what was recorded
+        if removed_all {
+            total = args.total_by_user_mapper.get();
+        }
+
+        let new_total = args.total_delegated_mapper.update(|total_del| {
             *total_del -= &total;
+
+            total_del.clone()
         });
-        args.total_by_user_mapper.update(|total_user| {
-            *total_user -= total;
+        let new_user_total = args.total_by_user_mapper.update(|total_user| {
+            *total_user -= &total;
 
             if *total_user == 0 {
                 let _ = args.all_delegators_mapper.swap_remove(&args.caller_id);
+                let _ = args.user_targets_mapper.swap_remove(&args.target_id);
             }
+
+            total_user.clone()
         });
+        self.record_stake_checkpoint(
+            &args.target_checkpoints_mapper,
+            &(&new_total + &total),
+            new_total,
+        );
+        self.record_stake_checkpoint(
+            &args.user_checkpoints_mapper,
+            &(&new_user_total + &total),
+            new_user_total,
+        );
 
         UniquePayments::new_from_payments(output_payments)
     }
+
+    /// The new amount takes effect from the next epoch, only the last change of an epoch is kept.
+    /// Stake from before checkpoints were recorded is backfilled on the first change.
+    fn record_stake_checkpoint(
+        &self,
+        checkpoints_mapper: &VecMapper<StakeCheckpoint<Self::Api>>,
+        previous_amount: &BigUint,
+        amount: BigUint,
+    ) {
+        if checkpoints_mapper.is_empty() && *previous_amount > 0 {
+            let _ = checkpoints_mapper.push(&StakeCheckpoint {
+                epoch: 0,
+                amount: previous_amount.clone(),
+            });
+        }
+
+        let effective_epoch = self.blockchain().get_block_epoch() + 1;
+        let checkpoint = StakeCheckpoint {
+            epoch: effective_epoch,
+            amount,
+        };
+
+        let len = checkpoints_mapper.len();
+        if len > 0 && checkpoints_mapper.get(len).epoch == effective_epoch {
+            checkpoints_mapper.set(len, &checkpoint);
+        } else {
+            let _ = checkpoints_mapper.push(&checkpoint);
+        }
+    }
+
+    /// Binary search for the last checkpoint at or before the given epoch.
+    /// Without checkpoints the stake has not changed since before they were recorded.
+    fn get_stake_at_epoch(
+        &self,
+        checkpoints_mapper: &VecMapper<StakeCheckpoint<Self::Api>>,
+        current_amount_mapper: &SingleValueMapper<BigUint>,
+        epoch: Epoch,
+    ) -> BigUint {
+        if checkpoints_mapper.is_empty() {
+            return current_amount_mapper.get();
+        }
+
+        let mut low = 1;
+        let mut high = checkpoints_mapper.len();
+        let mut result = BigUint::zero();
+        while low <= high {
+            let mid = low + (high - low) / 2;
+            let checkpoint = checkpoints_mapper.get(mid);
+            if checkpoint.epoch <= epoch {
+                result = checkpoint.amount;
+                low = mid + 1;
+            } else {
+                high = mid - 1;
+            }
+        }
+
+        result
+    }
 }
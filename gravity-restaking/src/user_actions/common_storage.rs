@@ -10,4 +10,8 @@ pub trait CommonStorageModule {
     #[view(getUserTokens)]
     #[storage_mapper("userTokens")]
     fn user_tokens(&self, user_id: AddressId) -> SingleValueMapper<UniquePayments<Self::Api>>;
+
+    /// Tokens held for users, whether idle, delegated, locked or unbonding
+    #[storage_mapper("totalDeposited")]
+    fn total_deposited(&self) -> SingleValueMapper<UniquePayments<Self::Api>>;
 }
@@ -0,0 +1,340 @@
+multiversx_sc::imports!();
+multiversx_sc::derive_imports!();
+
+pub const REWARD_PER_SHARE_PRECISION: u64 = 1_000_000_000_000_000_000;
+
+/// Moved stake waiting for the given claim of its delegation contract.
+/// The rewards of that claim accrued before the stake arrived, so it only shares in later ones.
+#[derive(TypeAbi, TopEncode, TopDecode, NestedEncode, NestedDecode)]
+pub struct PendingMovedStake<M: ManagedTypeApi> {
+    pub amount: BigUint<M>,
+    pub activation_claim: u64,
+}
+
+/// Staking rewards produced at the delegation contracts by the stake moved in through moveStakeToReStaking.
+/// Rewards are attributed with a reward-per-share index per delegation contract,
+/// the stake moved in by each user being their share.
+/// Moved stake is credited as EGLD and earns, idle or restaked, until that EGLD is withdrawn.
+/// The share of the stake held at the delegation contract that no user earns on,
+/// i.e. stake waiting for its first claim or withdrawn since, goes to the protocol.
+#[multiversx_sc::module]
+pub trait DelegationRewardsModule:
+    crate::token_whitelist::TokenWhitelistModule
+    + super::common_actions::CommonActionsModule
+    + super::common_storage::CommonStorageModule
+    + crate::events::user_events::UserEventsModule
+{
+    /// Collected rewards are re-delegated to the same delegation contract instead of being credited as EGLD
+    #[endpoint(setAutoCompound)]
+    fn set_auto_compound(&self, enabled: bool) {
+        let caller = self.blockchain().get_caller();
+        let caller_id = self.user_ids().get_id_non_zero(&caller);
+        self.auto_compound(caller_id).set(enabled);
+
+        self.emit_set_auto_compound_event(caller, enabled);
+    }
+
+    #[view(getDelegationContracts)]
+    fn get_delegation_contracts(&self) -> MultiValueEncoded<ManagedAddress> {
+        let mut result = MultiValueEncoded::new();
+        for delegation in self.delegation_contracts().iter() {
+            result.push(delegation);
+        }
+
+        result
+    }
+
+    /// Includes the stake still waiting for the next claim
+    #[view(getMovedStake)]
+    fn get_moved_stake(&self, user: ManagedAddress, delegation: ManagedAddress) -> BigUint {
+        let user_id = self.user_ids().get_id(&user);
+        if user_id == NULL_ID {
+            return BigUint::zero();
+        }
+
+        let pending_mapper = self.pending_moved_stake(&delegation, user_id);
+        let pending_amount = if pending_mapper.is_empty() {
+            BigUint::zero()
+        } else {
+            pending_mapper.get().amount
+        };
+
+        self.moved_stake(&delegation, user_id).get() + pending_amount
+    }
+
+    #[view(getPendingDelegationRewards)]
+    fn get_pending_delegation_rewards(
+        &self,
+        user: ManagedAddress,
+        delegation: ManagedAddress,
+    ) -> BigUint {
+        let user_id = self.user_ids().get_id(&user);
+        if user_id == NULL_ID {
+            return BigUint::zero();
+        }
+
+        self.get_pending_delegation_rewards_by_id(&delegation, user_id)
+    }
+
+    #[view(isAutoCompound)]
+    fn is_auto_compound(&self, user: ManagedAddress) -> bool {
+        let user_id = self.user_ids().get_id(&user);
+        if user_id == NULL_ID {
+            return false;
+        }
+
+        self.auto_compound(user_id).get()
+    }
+
+    /// The stake only starts earning after the next claim
+    fn add_moved_stake(&self, delegation: &ManagedAddress, user_id: AddressId, amount: &BigUint) {
+        self.settle_delegation_rewards(delegation, user_id);
+
+        let activation_claim = self.delegation_claim_nonce(delegation).get() + 1;
+        let pending_mapper = self.pending_moved_stake(delegation, user_id);
+        let mut pending = if pending_mapper.is_empty() {
+            PendingMovedStake {
+                amount: BigUint::zero(),
+                activation_claim,
+            }
+        } else {
+            pending_mapper.get()
+        };
+        pending.amount += amount;
+        pending_mapper.set(pending);
+        let delegation_stake = self.get_delegation_stake(delegation);
+        self.delegation_stake(delegation)
+            .set(delegation_stake + amount);
+        self.total_pending_moved_stake(delegation)
+            .update(|total| *total += amount);
+
+        let _ = self.delegation_contracts().insert(delegation.clone());
+        let _ = self
+            .delegation_contracts_of_user(user_id)
+            .insert(delegation.clone());
+    }
+
+    /// Rewards are shared by the whole stake held at the delegation contract.
+    /// Returns the part attributed to the moved stake of the users.
+    fn distribute_delegation_rewards(
+        &self,
+        delegation: &ManagedAddress,
+        rewards: &BigUint,
+    ) -> BigUint {
+        let delegation_stake = self.get_delegation_stake(delegation);
+        if delegation_stake == 0 {
+            return BigUint::zero();
+        }
+
+        let reward_increase = rewards.clone() * REWARD_PER_SHARE_PRECISION / delegation_stake;
+        self.reward_per_share(delegation)
+            .update(|reward_per_share| *reward_per_share += &reward_increase);
+
+        self.total_moved_stake(delegation).get() * reward_increase / REWARD_PER_SHARE_PRECISION
+    }
+
+    /// Stake moved in before it was tracked is at least the moved stake of the users
+    fn get_delegation_stake(&self, delegation: &ManagedAddress) -> BigUint {
+        let delegation_stake = self.delegation_stake(delegation).get();
+        let users_stake = self.total_moved_stake(delegation).get()
+            + self.total_pending_moved_stake(delegation).get();
+
+        core::cmp::max(delegation_stake, users_stake)
+    }
+
+    /// To be called after the rewards of a claim were distributed
+    fn activate_pending_moved_stake(&self, delegation: &ManagedAddress) {
+        let claim_nonce = self.delegation_claim_nonce(delegation).update(|nonce| {
+            *nonce += 1;
+
+            *nonce
+        });
+        self.reward_per_share_at_claim(delegation, claim_nonce)
+            .set(self.reward_per_share(delegation).get());
+
+        let pending_total = self.total_pending_moved_stake(delegation).take();
+        self.total_moved_stake(delegation)
+            .update(|total| *total += pending_total);
+    }
+
+    fn settle_delegation_rewards(&self, delegation: &ManagedAddress, user_id: AddressId) {
+        let new_rewards = self.get_unsettled_delegation_rewards(delegation, user_id);
+        if new_rewards > 0 {
+            self.settled_delegation_rewards(delegation, user_id)
+                .update(|rewards| *rewards += new_rewards);
+        }
+
+        if let Some(pending) = self.get_activated_pending_moved_stake(delegation, user_id) {
+            self.pending_moved_stake(delegation, user_id).clear();
+            self.moved_stake(delegation, user_id)
+                .update(|moved_stake| *moved_stake += pending.amount);
+        }
+
+        self.user_reward_per_share(delegation, user_id)
+            .set(self.reward_per_share(delegation).get());
+    }
+
+    /// Withdrawn EGLD is taken out of the moved stake first, starting with the stake not earning yet
+    fn remove_withdrawn_moved_stake(&self, user_id: AddressId, withdrawn_egld: &BigUint) {
+        let mut remaining_withdrawn = withdrawn_egld.clone();
+        for delegation in self.delegation_contracts_of_user(user_id).iter() {
+            if remaining_withdrawn == 0 {
+                return;
+            }
+
+            self.settle_delegation_rewards(&delegation, user_id);
+
+            let pending_mapper = self.pending_moved_stake(&delegation, user_id);
+            if !pending_mapper.is_empty() {
+                let mut pending = pending_mapper.get();
+                let removed_pending =
+                    core::cmp::min(pending.amount.clone(), remaining_withdrawn.clone());
+                remaining_withdrawn -= &removed_pending;
+                self.total_pending_moved_stake(&delegation)
+                    .update(|total| *total -= &removed_pending);
+                if removed_pending == pending.amount {
+                    pending_mapper.clear();
+                } else {
+                    pending.amount -= removed_pending;
+                    pending_mapper.set(pending);
+                }
+            }
+
+            let moved_stake_mapper = self.moved_stake(&delegation, user_id);
+            let moved_stake = moved_stake_mapper.get();
+            let removed_moved_stake =
+                core::cmp::min(moved_stake.clone(), remaining_withdrawn.clone());
+            if removed_moved_stake > 0 {
+                remaining_withdrawn -= &removed_moved_stake;
+                self.total_moved_stake(&delegation)
+                    .update(|total| *total -= &removed_moved_stake);
+                moved_stake_mapper.set(moved_stake - removed_moved_stake);
+            }
+        }
+    }
+
+    fn take_delegation_rewards(&self, delegation: &ManagedAddress, user_id: AddressId) -> BigUint {
+        self.settle_delegation_rewards(delegation, user_id);
+
+        self.settled_delegation_rewards(delegation, user_id).take()
+    }
+
+    fn get_pending_delegation_rewards_by_id(
+        &self,
+        delegation: &ManagedAddress,
+        user_id: AddressId,
+    ) -> BigUint {
+        self.settled_delegation_rewards(delegation, user_id).get()
+            + self.get_unsettled_delegation_rewards(delegation, user_id)
+    }
+
+    fn get_unsettled_delegation_rewards(
+        &self,
+        delegation: &ManagedAddress,
+        user_id: AddressId,
+    ) -> BigUint {
+        let mut moved_stake = self.moved_stake(delegation, user_id).get();
+        let mut user_reward_per_share = self.user_reward_per_share(delegation, user_id).get();
+        let mut rewards = BigUint::zero();
+        if let Some(pending) = self.get_activated_pending_moved_stake(delegation, user_id) {
+            let activation_reward_per_share = self
+                .reward_per_share_at_claim(delegation, pending.activation_claim)
+                .get();
+            rewards += &moved_stake * &(&activation_reward_per_share - &user_reward_per_share)
+                / REWARD_PER_SHARE_PRECISION;
+
+            moved_stake += pending.amount;
+            user_reward_per_share = activation_reward_per_share;
+        }
+
+        if moved_stake == 0 {
+            return rewards;
+        }
+
+        let reward_per_share = self.reward_per_share(delegation).get();
+
+        rewards
+            + moved_stake * (reward_per_share - user_reward_per_share) / REWARD_PER_SHARE_PRECISION
+    }
+
+    fn get_activated_pending_moved_stake(
+        &self,
+        delegation: &ManagedAddress,
+        user_id: AddressId,
+    ) -> Option<PendingMovedStake<Self::Api>> {
+        let pending_mapper = self.pending_moved_stake(delegation, user_id);
+        if pending_mapper.is_empty() {
+            return None;
+        }
+
+        let pending = pending_mapper.get();
+        if pending.activation_claim > self.delegation_claim_nonce(delegation).get() {
+            return None;
+        }
+
+        Some(pending)
+    }
+
+    #[storage_mapper("delegationContracts")]
+    fn delegation_contracts(&self) -> UnorderedSetMapper<ManagedAddress>;
+
+    #[storage_mapper("delegationContractsOfUser")]
+    fn delegation_contracts_of_user(
+        &self,
+        user_id: AddressId,
+    ) -> UnorderedSetMapper<ManagedAddress>;
+
+    #[storage_mapper("movedStake")]
+    fn moved_stake(
+        &self,
+        delegation: &ManagedAddress,
+        user_id: AddressId,
+    ) -> SingleValueMapper<BigUint>;
+
+    #[storage_mapper("totalMovedStake")]
+    fn total_moved_stake(&self, delegation: &ManagedAddress) -> SingleValueMapper<BigUint>;
+
+    /// The stake held by this contract at the delegation contract, which only ever grows
+    #[storage_mapper("delegationStake")]
+    fn delegation_stake(&self, delegation: &ManagedAddress) -> SingleValueMapper<BigUint>;
+
+    #[storage_mapper("pendingMovedStake")]
+    fn pending_moved_stake(
+        &self,
+        delegation: &ManagedAddress,
+        user_id: AddressId,
+    ) -> SingleValueMapper<PendingMovedStake<Self::Api>>;
+
+    #[storage_mapper("totalPendingMovedStake")]
+    fn total_pending_moved_stake(&self, delegation: &ManagedAddress) -> SingleValueMapper<BigUint>;
+
+    #[storage_mapper("delegationClaimNonce")]
+    fn delegation_claim_nonce(&self, delegation: &ManagedAddress) -> SingleValueMapper<u64>;
+
+    #[storage_mapper("rewardPerShareAtClaim")]
+    fn reward_per_share_at_claim(
+        &self,
+        delegation: &ManagedAddress,
+        claim_nonce: u64,
+    ) -> SingleValueMapper<BigUint>;
+
+    #[storage_mapper("rewardPerShare")]
+    fn reward_per_share(&self, delegation: &ManagedAddress) -> SingleValueMapper<BigUint>;
+
+    #[storage_mapper("userRewardPerShare")]
+    fn user_reward_per_share(
+        &self,
+        delegation: &ManagedAddress,
+        user_id: AddressId,
+    ) -> SingleValueMapper<BigUint>;
+
+    #[storage_mapper("settledDelegationRewards")]
+    fn settled_delegation_rewards(
+        &self,
+        delegation: &ManagedAddress,
+        user_id: AddressId,
+    ) -> SingleValueMapper<BigUint>;
+
+    #[storage_mapper("autoCompound")]
+    fn auto_compound(&self, user_id: AddressId) -> SingleValueMapper<bool>;
+}
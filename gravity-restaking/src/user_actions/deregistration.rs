@@ -0,0 +1,288 @@
+use super::{
+    common_actions::RemoveDelegationArgs, receipt_token::ReceiptTargetType, sovereign::SovState,
+};
+
+multiversx_sc::imports!();
+
+#[multiversx_sc::module]
+pub trait DeregistrationModule:
+    super::user::UserModule
+    + super::redelegate::RedelegateModule
+    + super::receipt_token::ReceiptTokenModule
+    + super::shared_security::SharedSecurityModule
+    + super::operator_allocation::OperatorAllocationModule
+    + super::validator_metadata::ValidatorMetadataModule
+    + super::ownership_transfer::OwnershipTransferModule
+    + super::security_snapshot::SecuritySnapshotModule
+    + crate::token_whitelist::TokenWhitelistModule
+    + super::validator::ValidatorModule
+    + super::self_bond::SelfBondModule
+    + super::sovereign::SovereignModule
+    + super::sov_allow_list::SovAllowListModule
+    + super::unbond::UnbondModule
+    + super::common_actions::CommonActionsModule
+    + super::common_storage::CommonStorageModule
+    + crate::events::user_events::UserEventsModule
+    + crate::events::validator_events::ValidatorEventsModule
+    + crate::events::sov_events::SovEventsModule
+    + utils::UtilsModule
+{
+    /// The first call stops new delegations.
+    /// The registration is removed once all the delegators have exited.
+    #[endpoint(unRegisterValidator)]
+    fn unregister_validator(&self) {
+        let caller = self.blockchain().get_caller();
+        let validator_id = self.resolve_validator_id_non_zero(&caller);
+        for bls_key in self.bls_keys(validator_id).iter() {
+            require!(
+                !self.is_key_jailed(&bls_key),
+                "Cannot unregister with jailed keys"
+            );
+        }
+
+        if !self.all_delegators(validator_id).is_empty() {
+            let unregistering_mapper = self.validator_unregistering(validator_id);
+            require!(!unregistering_mapper.get(), "Delegators still have to exit");
+
+            unregistering_mapper.set(true);
+            self.emit_validator_start_unregister_event(caller);
+
+            return;
+        }
+
+        self.clear_validator(validator_id, &caller);
+
+        self.emit_validator_unregister_event(caller);
+    }
+
+    /// The first call moves the chain to Sunset.
+    /// The registration is removed once all the delegators have exited.
+    #[endpoint(unRegister)]
+    fn unregister(&self) {
+        let caller = self.blockchain().get_caller();
+        let sov_id = self.resolve_sov_id(&caller);
+        require!(sov_id != NULL_ID, "Unknown sovereign chain");
+
+        if !self.all_sov_delegators(sov_id).is_empty()
+            || !self.all_shared_sov_delegators(sov_id).is_empty()
+            || !self.sov_allocation_opt_ins(sov_id).is_empty()
+        {
+            require!(
+                self.sov_state(sov_id).get() != SovState::Sunset,
+                "Delegators still have to exit"
+            );
+
+            self.change_sov_state(sov_id, &caller, SovState::Sunset);
+
+            return;
+        }
+
+        self.clear_sov(sov_id, &caller);
+
+        self.emit_sov_unregister_event(caller);
+    }
+
+    /// Revokes the delegation of up to max_delegators delegators, which then go through unbonding.
+    /// Tokenized positions are kept for the receipt holders to unbond by burning their receipts.
+    #[endpoint(exitValidatorDelegators)]
+    fn exit_validator_delegators(&self, validator: ManagedAddress, max_delegators: usize) {
+        let validator_id = self.resolve_validator_id_non_zero(&validator);
+        require!(
+            self.validator_unregistering(validator_id).get(),
+            "Validator is not unregistering"
+        );
+
+        let delegators_mapper = self.all_delegators(validator_id);
+        for _ in 0..max_delegators {
+            if delegators_mapper.is_empty() {
+                break;
+            }
+
+            let user_id = delegators_mapper.get_by_index(1);
+            let tokens = self.get_payments_or_default(&self.delegated_by(user_id, validator_id));
+            let args = RemoveDelegationArgs {
+                total_delegated_mapper: self.total_delegated_amount(validator_id),
+                total_by_user_mapper: self.total_by_user(user_id, validator_id),
+                target_checkpoints_mapper: self.validator_stake_checkpoints(validator_id),
+                user_checkpoints_mapper: self
+                    .user_validator_stake_checkpoints(user_id, validator_id),
+                all_delegators_mapper: &mut self.all_delegators(validator_id),
+                user_targets_mapper: &mut self.validators_of_user(user_id),
+                delegated_by_mapper: self.delegated_by(user_id, validator_id),
+                tokens: self.to_payments_multi_value(tokens.into_payments()),
+                target_id: validator_id,
+                caller_id: user_id,
+            };
+            let output_unique_payments = self.remove_delegation(args);
+            self.sync_validator_allocation_capacity(user_id, validator_id);
+            if self.is_receipt_holder(user_id) {
+                self.add_to_receipt_unbonding_pool(
+                    ReceiptTargetType::Validator,
+                    validator_id,
+                    &validator,
+                    output_unique_payments.clone(),
+                );
+            } else {
+                self.add_unbond_tokens(user_id, output_unique_payments.clone());
+            }
+
+            let user = unsafe { self.user_ids().get_address(user_id).unwrap_unchecked() };
+            self.emit_revoke_validator_event(user, validator.clone(), output_unique_payments);
+        }
+    }
+
+    /// Revokes the delegation of up to max_delegators delegators, which then go through unbonding.
+    /// Shared security allocations are deallocated, and released after the unbond period.
+    /// Allocation opt-ins are removed last.
+    #[endpoint(exitSovDelegators)]
+    fn exit_sov_delegators(&self, sov_address: ManagedAddress, max_delegators: usize) {
+        let sov_id = self.resolve_sov_id_non_zero(&sov_address);
+        require!(
+            self.sov_state(sov_id).get() == SovState::Sunset,
+            "Sovereign chain is not in sunset"
+        );
+
+        let delegators_mapper = self.all_sov_delegators(sov_id);
+        let shared_delegators_mapper = self.all_shared_sov_delegators(sov_id);
+        let opt_ins_mapper = self.sov_allocation_opt_ins(sov_id);
+        for _ in 0..max_delegators {
+            if !delegators_mapper.is_empty() {
+                let user_id = delegators_mapper.get_by_index(1);
+                self.exit_sov_delegator(sov_id, &sov_address, user_id);
+            } else if !shared_delegators_mapper.is_empty() {
+                let user_id = shared_delegators_mapper.get_by_index(1);
+                self.exit_shared_sov_delegator(sov_id, &sov_address, user_id);
+            } else if !opt_ins_mapper.is_empty() {
+                let (user_id, validator_id) = opt_ins_mapper.get_by_index(1);
+                self.allocation_opt_in(user_id, validator_id, sov_id)
+                    .clear();
+                self.update_allocation_opt_in(user_id, validator_id, sov_id);
+            } else {
+                break;
+            }
+        }
+    }
+
+    fn exit_sov_delegator(
+        &self,
+        sov_id: AddressId,
+        sov_address: &ManagedAddress,
+        user_id: AddressId,
+    ) {
+        let tokens = self.get_payments_or_default(&self.delegated_sov_by(user_id, sov_id));
+        let args = RemoveDelegationArgs {
+            total_delegated_mapper: self.total_delegated_sov_amount(sov_id),
+            total_by_user_mapper: self.total_sov_by_user(user_id, sov_id),
+            target_checkpoints_mapper: self.sov_stake_checkpoints(sov_id),
+            user_checkpoints_mapper: self.user_sov_stake_checkpoints(user_id, sov_id),
+            all_delegators_mapper: &mut self.all_sov_delegators(sov_id),
+            user_targets_mapper: &mut self.sovs_of_user(user_id),
+            delegated_by_mapper: self.delegated_sov_by(user_id, sov_id),
+            tokens: self.to_payments_multi_value(tokens.into_payments()),
+            target_id: sov_id,
+            caller_id: user_id,
+        };
+        let output_unique_payments = self.remove_delegation(args);
+        if self.is_receipt_holder(user_id) {
+            self.add_to_receipt_unbonding_pool(
+                ReceiptTargetType::Sovereign,
+                sov_id,
+                sov_address,
+                output_unique_payments.clone(),
+            );
+        } else {
+            self.add_unbond_tokens(user_id, output_unique_payments.clone());
+        }
+
+        let user = unsafe { self.user_ids().get_address(user_id).unwrap_unchecked() };
+        self.emit_revoke_sov_event(user, sov_address.clone(), output_unique_payments);
+    }
+
+    fn exit_shared_sov_delegator(
+        &self,
+        sov_id: AddressId,
+        sov_address: &ManagedAddress,
+        user_id: AddressId,
+    ) {
+        let tokens = self.get_payments_or_default(&self.shared_sov_allocation(user_id, sov_id));
+        let args = RemoveDelegationArgs {
+            total_delegated_mapper: self.total_delegated_sov_amount(sov_id),
+            total_by_user_mapper: self.total_shared_sov_by_user(user_id, sov_id),
+            target_checkpoints_mapper: self.sov_stake_checkpoints(sov_id),
+            user_checkpoints_mapper: self.user_shared_sov_stake_checkpoints(user_id, sov_id),
+            all_delegators_mapper: &mut self.all_shared_sov_delegators(sov_id),
+            user_targets_mapper: &mut self.shared_sovs_of_user(user_id),
+            delegated_by_mapper: self.shared_sov_allocation(user_id, sov_id),
+            tokens: self.to_payments_multi_value(tokens.into_payments()),
+            target_id: sov_id,
+            caller_id: user_id,
+        };
+        let output_payments = self.remove_delegation(args).into_payments();
+        self.release_shared_deallocations(user_id);
+        self.start_shared_deallocation(user_id, sov_id, &output_payments);
+
+        let user = unsafe { self.user_ids().get_address(user_id).unwrap_unchecked() };
+        self.emit_deallocate_shared_stake_event(user, sov_address.clone(), output_payments);
+    }
+
+    fn clear_validator(&self, validator_id: AddressId, validator: &ManagedAddress) {
+        for sov_id in self.operator_sovs(validator_id).iter() {
+            let allocated = self.operator_allocation(validator_id, sov_id).take();
+            self.total_operator_allocated_sov(sov_id)
+                .update(|total| *total -= allocated);
+            let _ = self.all_sov_operators(sov_id).swap_remove(&validator_id);
+        }
+        self.operator_sovs(validator_id).clear();
+
+        for bls_key in self.bls_keys(validator_id).iter() {
+            self.bls_key_owner(&bls_key).clear();
+            self.bls_key_status(&bls_key).clear();
+        }
+        self.bls_keys(validator_id).clear();
+
+        let config = self.validator_config(validator_id).take();
+        self.id_for_name(&config.name).clear();
+        self.validator_metadata(validator_id).clear();
+        self.pending_validator_owner(validator_id).clear();
+        self.validator_unregistering(validator_id).clear();
+        self.total_delegated_amount(validator_id).clear();
+
+        self.validator_id_of_owner(validator).clear();
+        self.validator_owner(validator_id).clear();
+        if let Some(registration_address) = self.validator_id().get_address(validator_id) {
+            let _ = self.validator_id().remove_by_address(&registration_address);
+        }
+    }
+
+    fn clear_sov(&self, sov_id: AddressId, sov_address: &ManagedAddress) {
+        for validator_id in self.all_sov_operators(sov_id).iter() {
+            self.operator_allocation(validator_id, sov_id).clear();
+            self.allocation_capacity(validator_id, sov_id).clear();
+            let _ = self.operator_sovs(validator_id).swap_remove(&sov_id);
+        }
+        self.all_sov_operators(sov_id).clear();
+        self.total_operator_allocated_sov(sov_id).clear();
+
+        let sov_info = self.sov_info(sov_id).take();
+        self.sov_chain_for_name(&sov_info.name).clear();
+        self.pending_sov_owner(sov_id).clear();
+        self.sov_state(sov_id).clear();
+        self.total_delegated_sov_amount(sov_id).clear();
+        self.sov_accepted_tokens(sov_id).clear();
+        self.sov_min_delegation(sov_id).clear();
+        self.sov_min_total_security(sov_id).clear();
+        self.sov_target_validator_count(sov_id).clear();
+        self.sov_allow_list_enabled(sov_id).clear();
+        self.sov_allow_list(sov_id).clear();
+        self.sov_max_per_delegator(sov_id).clear();
+        self.security_bridge(sov_id).clear();
+        self.last_snapshot_nonce(sov_id).clear();
+        self.last_snapshot_epoch(sov_id).clear();
+
+        self.sov_id_of_owner(sov_address).clear();
+        self.sov_owner(sov_id).clear();
+        if let Some(registration_address) = self.sov_id().get_address(sov_id) {
+            let _ = self.sov_id().remove_by_address(&registration_address);
+        }
+    }
+}
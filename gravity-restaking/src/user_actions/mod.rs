@@ -1,7 +1,22 @@
+pub mod allocation_capacity;
+pub mod bls_key_status;
 pub mod call_delegation;
 pub mod common_actions;
 pub mod common_storage;
+pub mod delegation_rewards;
+pub mod deregistration;
+pub mod operator_allocation;
+pub mod own_delegation;
+pub mod ownership_transfer;
+pub mod receipt_token;
+pub mod redelegate;
+pub mod security_snapshot;
+pub mod self_bond;
+pub mod shared_security;
+pub mod sov_allow_list;
 pub mod sovereign;
 pub mod unbond;
 pub mod user;
+pub mod user_targets;
 pub mod validator;
+pub mod validator_metadata;
@@ -0,0 +1,265 @@
+use super::validator::{Percent, MAX_PERCENT};
+
+multiversx_sc::imports!();
+
+pub type AllocationMultiValue<M> = MultiValue2<ManagedAddress<M>, BigUint<M>>;
+
+/// Keeps the security snapshot of a sovereign chain within the gas limit
+pub const MAX_SOV_OPERATORS: usize = 100;
+
+#[multiversx_sc::module]
+pub trait OperatorAllocationModule:
+    crate::token_whitelist::TokenWhitelistModule
+    + super::allocation_capacity::AllocationCapacityModule
+    + super::validator::ValidatorModule
+    + super::self_bond::SelfBondModule
+    + super::sovereign::SovereignModule
+    + super::sov_allow_list::SovAllowListModule
+    + super::common_actions::CommonActionsModule
+    + super::common_storage::CommonStorageModule
+    + crate::events::user_events::UserEventsModule
+    + crate::events::validator_events::ValidatorEventsModule
+    + utils::UtilsModule
+{
+    /// Percent of the caller's delegation that the validator may allocate to the given sovereign.
+    /// Only the tokens accepted by the sovereign may be allocated.
+    #[endpoint(setAllocationOptIn)]
+    fn set_allocation_opt_in(
+        &self,
+        validator: ManagedAddress,
+        sov_name: ManagedBuffer,
+        max_percent: Percent,
+    ) {
+        require!(max_percent <= MAX_PERCENT, "Invalid percent");
+
+        let caller = self.blockchain().get_caller();
+        let caller_id = self.user_ids().get_id_non_zero(&caller);
+        let validator_id = self.resolve_validator_id_non_zero(&validator);
+        let sov_id = self.sov_chain_for_name(&sov_name).get();
+        self.require_valid_sov_id(sov_id);
+
+        if max_percent > 0 {
+            self.require_sov_accepts_delegation(sov_id);
+            self.require_allowed_for_sov(sov_id, &caller);
+        }
+
+        self.allocation_opt_in(caller_id, validator_id, sov_id)
+            .set(max_percent);
+        self.update_allocation_opt_in(caller_id, validator_id, sov_id);
+
+        let sov_address = unsafe { self.get_sov_owner(sov_id).unwrap_unchecked() };
+        self.emit_set_allocation_opt_in_event(caller, validator, sov_address, max_percent);
+    }
+
+    /// Sets the EGLD value of the delegated stake allocated to the sovereign.
+    /// 0 removes the allocation
+    #[endpoint(allocateToSovereign)]
+    fn allocate_to_sovereign(&self, sov_name: ManagedBuffer, amount: BigUint) {
+        let caller = self.blockchain().get_caller();
+        let validator_id = self.resolve_validator_id_non_zero(&caller);
+        let sov_id = self.sov_chain_for_name(&sov_name).get();
+        self.require_valid_sov_id(sov_id);
+
+        if amount > 0 {
+            self.require_validator_not_unregistering(validator_id);
+            self.require_sov_accepts_delegation(sov_id);
+            self.require_allowed_for_sov(sov_id, &caller);
+            require!(
+                self.is_self_bond_compliant_by_id(validator_id, &caller),
+                "Validator self-bond too low"
+            );
+            require!(
+                amount >= self.sov_min_delegation(sov_id).get(),
+                "Delegation below the sovereign chain minimum"
+            );
+            self.require_within_max_per_delegator(sov_id, &amount);
+        }
+
+        let capacity = self.allocation_capacity(validator_id, sov_id).get();
+        require!(amount <= capacity, "Not enough opted-in stake");
+
+        let allocation_mapper = self.operator_allocation(validator_id, sov_id);
+        let previous_amount = allocation_mapper.get();
+        let new_total_allocated = self.total_operator_allocated_sov(sov_id).update(|total| {
+            *total -= &previous_amount;
+            *total += &amount;
+
+            total.clone()
+        });
+        if amount > previous_amount {
+            if let Some(max_restaking_cap) = self.sov_info(sov_id).get().opt_max_restaking_cap {
+                require!(
+                    self.total_delegated_sov_amount(sov_id).get() + new_total_allocated
+                        <= max_restaking_cap,
+                    "Max delegated amount exceeded"
+                );
+            }
+        }
+
+        if amount == 0 {
+            allocation_mapper.clear();
+            let _ = self.operator_sovs(validator_id).swap_remove(&sov_id);
+            let _ = self.all_sov_operators(sov_id).swap_remove(&validator_id);
+        } else {
+            let mut sov_operators_mapper = self.all_sov_operators(sov_id);
+            let target_validator_count = self.sov_target_validator_count(sov_id).get();
+            require!(
+                target_validator_count == 0
+                    || sov_operators_mapper.contains(&validator_id)
+                    || sov_operators_mapper.len() < target_validator_count,
+                "Sovereign chain has enough validators"
+            );
+            require!(
+                sov_operators_mapper.contains(&validator_id)
+                    || sov_operators_mapper.len() < MAX_SOV_OPERATORS,
+                "Too many validators for the sovereign chain"
+            );
+
+            allocation_mapper.set(&amount);
+            let _ = self.operator_sovs(validator_id).insert(sov_id);
+            let _ = sov_operators_mapper.insert(validator_id);
+        }
+
+        let sov_address = unsafe { self.get_sov_owner(sov_id).unwrap_unchecked() };
+        self.try_activate_sov(sov_id, &sov_address);
+
+        self.emit_validator_allocate_to_sov_event(caller, sov_address, amount);
+    }
+
+    /// No tokens means any whitelisted token is accepted.
+    /// The allocation capacity of every opted-in validator is recomputed for the new tokens.
+    #[endpoint(setAcceptedTokens)]
+    fn set_accepted_tokens(&self, tokens: MultiValueEncoded<TokenIdentifier>) {
+        let caller = self.blockchain().get_caller();
+        let sov_id = self.resolve_sov_id_non_zero(&caller);
+
+        let mut accepted_tokens_mapper = self.sov_accepted_tokens(sov_id);
+        accepted_tokens_mapper.clear();
+
+        let mut accepted_tokens = ManagedVec::new();
+        for token_id in tokens {
+            self.require_token_in_whitelist(&token_id);
+
+            let _ = accepted_tokens_mapper.insert(token_id.clone());
+            accepted_tokens.push(token_id);
+        }
+
+        for (user_id, validator_id) in self.sov_allocation_opt_ins(sov_id).iter() {
+            self.update_allocation_opt_in(user_id, validator_id, sov_id);
+        }
+
+        self.emit_sov_set_accepted_tokens_event(caller, accepted_tokens);
+    }
+
+    #[view(getAllocationCapacity)]
+    fn get_allocation_capacity(
+        &self,
+        validator: ManagedAddress,
+        sov_address: ManagedAddress,
+    ) -> BigUint {
+        let validator_id = self.resolve_validator_id(&validator);
+        let sov_id = self.resolve_sov_id(&sov_address);
+        if validator_id == NULL_ID || sov_id == NULL_ID {
+            return BigUint::zero();
+        }
+
+        self.allocation_capacity(validator_id, sov_id).get()
+    }
+
+    /// Recomputes the user's contribution to the allocation capacity.
+    /// Meant for opt-ins made before the capacity was tracked.
+    #[endpoint(syncAllocationOptIn)]
+    fn sync_allocation_opt_in(
+        &self,
+        user: ManagedAddress,
+        validator: ManagedAddress,
+        sov_address: ManagedAddress,
+    ) {
+        let user_id = self.user_ids().get_id_non_zero(&user);
+        let validator_id = self.resolve_validator_id_non_zero(&validator);
+        let sov_id = self.resolve_sov_id_non_zero(&sov_address);
+
+        self.update_allocation_opt_in(user_id, validator_id, sov_id);
+    }
+
+    /// Pairs of (sov_address, allocated amount)
+    #[view(getOperatorAllocations)]
+    fn get_operator_allocations(
+        &self,
+        validator: ManagedAddress,
+    ) -> MultiValueEncoded<AllocationMultiValue<Self::Api>> {
+        let mut result = MultiValueEncoded::new();
+        let validator_id = self.resolve_validator_id(&validator);
+        if validator_id == NULL_ID {
+            return result;
+        }
+
+        for sov_id in self.operator_sovs(validator_id).iter() {
+            let sov_address = unsafe { self.get_sov_owner(sov_id).unwrap_unchecked() };
+            let amount = self.operator_allocation(validator_id, sov_id).get();
+            result.push((sov_address, amount).into());
+        }
+
+        result
+    }
+
+    /// Pairs of (sov_address, exposure), where the validator's allocation is split
+    /// between the delegators proportionally to their opted-in stake
+    #[view(getDelegatorExposure)]
+    fn get_delegator_exposure(
+        &self,
+        user: ManagedAddress,
+        validator: ManagedAddress,
+    ) -> MultiValueEncoded<AllocationMultiValue<Self::Api>> {
+        let mut result = MultiValueEncoded::new();
+        let user_id = self.user_ids().get_id(&user);
+        let validator_id = self.resolve_validator_id(&validator);
+        if user_id == NULL_ID || validator_id == NULL_ID {
+            return result;
+        }
+
+        for sov_id in self.operator_sovs(validator_id).iter() {
+            let user_capacity = self
+                .capacity_contribution(user_id, validator_id, sov_id)
+                .get();
+            if user_capacity == 0 {
+                continue;
+            }
+
+            let total_capacity = self.allocation_capacity(validator_id, sov_id).get();
+            let allocation = self.operator_allocation(validator_id, sov_id).get();
+            let mut exposure = allocation * &user_capacity / total_capacity;
+            if exposure > user_capacity {
+                exposure = user_capacity;
+            }
+
+            let sov_address = unsafe { self.get_sov_owner(sov_id).unwrap_unchecked() };
+            result.push((sov_address, exposure).into());
+        }
+
+        result
+    }
+
+    fn update_allocation_opt_in(
+        &self,
+        user_id: AddressId,
+        validator_id: AddressId,
+        sov_id: AddressId,
+    ) {
+        let delegated_tokens =
+            self.get_payments_or_default(&self.delegated_by(user_id, validator_id));
+        let capacity =
+            self.sync_capacity_contribution(user_id, validator_id, sov_id, &delegated_tokens);
+        self.clamp_operator_allocation(validator_id, sov_id, &capacity);
+
+        let mut opted_in_sovs_mapper = self.opted_in_sovs(user_id, validator_id);
+        let mut sov_opt_ins_mapper = self.sov_allocation_opt_ins(sov_id);
+        if self.allocation_opt_in(user_id, validator_id, sov_id).get() > 0 {
+            let _ = opted_in_sovs_mapper.insert(sov_id);
+            let _ = sov_opt_ins_mapper.insert((user_id, validator_id));
+        } else {
+            let _ = opted_in_sovs_mapper.swap_remove(&sov_id);
+            let _ = sov_opt_ins_mapper.swap_remove(&(user_id, validator_id));
+        }
+    }
+}
@@ -0,0 +1,81 @@
+use super::{common_actions::RemoveDelegationArgs, user::PaymentsMultiValue};
+
+multiversx_sc::imports!();
+
+#[multiversx_sc::module]
+pub trait OwnDelegationModule:
+    super::user::UserModule
+    + crate::token_whitelist::TokenWhitelistModule
+    + super::validator::ValidatorModule
+    + super::self_bond::SelfBondModule
+    + super::sovereign::SovereignModule
+    + super::unbond::UnbondModule
+    + super::common_actions::CommonActionsModule
+    + super::common_storage::CommonStorageModule
+    + crate::events::validator_events::ValidatorEventsModule
+    + crate::events::sov_events::SovEventsModule
+    + utils::UtilsModule
+{
+    /// Pairs of (token_id, nonce, amount)
+    #[endpoint(revokeOwnDelegation)]
+    fn revoke_own_delegation(&self, tokens: PaymentsMultiValue<Self::Api>) {
+        self.require_non_empty_args(&tokens);
+
+        let validator = self.blockchain().get_caller();
+        let validator_id = self.resolve_validator_id_non_zero(&validator);
+        let user_id_of_validator = self.user_ids().get_id_non_zero(&validator);
+
+        let args = RemoveDelegationArgs {
+            total_delegated_mapper: self.total_delegated_amount(validator_id),
+            total_by_user_mapper: self.total_by_user(user_id_of_validator, validator_id),
+            target_checkpoints_mapper: self.validator_stake_checkpoints(validator_id),
+            user_checkpoints_mapper: self
+                .user_validator_stake_checkpoints(user_id_of_validator, validator_id),
+            all_delegators_mapper: &mut self.all_delegators(validator_id),
+            user_targets_mapper: &mut self.validators_of_user(user_id_of_validator),
+            delegated_by_mapper: self.delegated_by(user_id_of_validator, validator_id),
+            tokens,
+            target_id: validator_id,
+            caller_id: user_id_of_validator,
+        };
+        let output_unique_payments = self.remove_delegation(args);
+        self.sync_validator_allocation_capacity(user_id_of_validator, validator_id);
+        self.require_self_bond_after_revoke(validator_id, &validator, &validator);
+        self.add_unbond_tokens(user_id_of_validator, output_unique_payments.clone());
+
+        self.emit_validator_revoke_own_delegation_event(
+            validator,
+            output_unique_payments.into_payments(),
+        );
+    }
+
+    /// Pairs of (token_id, nonce, amount)
+    #[endpoint(withdrawOwnSecurityFunds)]
+    fn withdraw_own_security_funds(&self, tokens: PaymentsMultiValue<Self::Api>) {
+        self.require_non_empty_args(&tokens);
+
+        let sov_chain = self.blockchain().get_caller();
+        let sov_id = self.resolve_sov_id_non_zero(&sov_chain);
+        let user_id_of_sov_chain = self.user_ids().get_id_non_zero(&sov_chain);
+
+        let args = RemoveDelegationArgs {
+            total_delegated_mapper: self.total_delegated_sov_amount(sov_id),
+            total_by_user_mapper: self.total_sov_by_user(user_id_of_sov_chain, sov_id),
+            target_checkpoints_mapper: self.sov_stake_checkpoints(sov_id),
+            user_checkpoints_mapper: self.user_sov_stake_checkpoints(user_id_of_sov_chain, sov_id),
+            all_delegators_mapper: &mut self.all_sov_delegators(sov_id),
+            user_targets_mapper: &mut self.sovs_of_user(user_id_of_sov_chain),
+            delegated_by_mapper: self.delegated_sov_by(user_id_of_sov_chain, sov_id),
+            tokens,
+            target_id: sov_id,
+            caller_id: user_id_of_sov_chain,
+        };
+        let output_unique_payments = self.remove_delegation(args);
+        self.add_unbond_tokens(user_id_of_sov_chain, output_unique_payments.clone());
+
+        self.emit_sov_withdraw_own_security_funds_event(
+            sov_chain,
+            output_unique_payments.into_payments(),
+        );
+    }
+}
@@ -0,0 +1,210 @@
+use multiversx_sc::api::StorageMapperApi;
+
+use crate::unique_payments::UniquePayments;
+
+use super::common_actions::StakeCheckpoint;
+
+multiversx_sc::imports!();
+
+pub struct MoveDelegationArgs<'a, S: StorageMapperApi> {
+    pub from_delegated_by_mapper: SingleValueMapper<S, UniquePayments<S>>,
+    pub to_delegated_by_mapper: SingleValueMapper<S, UniquePayments<S>>,
+    pub from_total_by_user_mapper: SingleValueMapper<S, BigUint<S>>,
+    pub to_total_by_user_mapper: SingleValueMapper<S, BigUint<S>>,
+    pub from_user_checkpoints_mapper: VecMapper<S, StakeCheckpoint<S>>,
+    pub to_user_checkpoints_mapper: VecMapper<S, StakeCheckpoint<S>>,
+    pub all_delegators_mapper: &'a mut UnorderedSetMapper<S, AddressId>,
+    pub from_user_targets_mapper: &'a mut UnorderedSetMapper<S, AddressId>,
+    pub to_user_targets_mapper: &'a mut UnorderedSetMapper<S, AddressId>,
+    pub from_user_id: AddressId,
+    pub to_user_id: AddressId,
+    pub target_id: AddressId,
+}
+
+#[multiversx_sc::module]
+pub trait OwnershipTransferModule:
+    crate::token_whitelist::TokenWhitelistModule
+    + super::validator::ValidatorModule
+    + super::sovereign::SovereignModule
+    + super::common_actions::CommonActionsModule
+    + super::common_storage::CommonStorageModule
+    + crate::events::validator_events::ValidatorEventsModule
+    + crate::events::sov_events::SovEventsModule
+    + utils::UtilsModule
+{
+    #[endpoint(proposeValidatorOwner)]
+    fn propose_validator_owner(&self, new_owner: ManagedAddress) {
+        let caller = self.blockchain().get_caller();
+        let validator_id = self.resolve_validator_id_non_zero(&caller);
+        require!(new_owner != caller, "Already the owner");
+        require!(
+            self.resolve_validator_id(&new_owner) == NULL_ID,
+            "New owner is already a validator"
+        );
+
+        self.pending_validator_owner(validator_id).set(&new_owner);
+
+        self.emit_validator_propose_owner_event(caller, new_owner);
+    }
+
+    #[endpoint(acceptValidatorOwnership)]
+    fn accept_validator_ownership(&self, current_owner: ManagedAddress) {
+        let caller = self.blockchain().get_caller();
+        let validator_id = self.resolve_validator_id_non_zero(&current_owner);
+        self.require_pending_owner(&self.pending_validator_owner(validator_id), &caller);
+        require!(
+            self.resolve_validator_id(&caller) == NULL_ID,
+            "New owner is already a validator"
+        );
+
+        self.pending_validator_owner(validator_id).clear();
+        self.set_validator_owner(validator_id, &current_owner, &caller);
+
+        let old_user_id = self.user_ids().get_id(&current_owner);
+        if old_user_id != NULL_ID && !self.delegated_by(old_user_id, validator_id).is_empty() {
+            let new_user_id = self.user_ids().get_id_or_insert(&caller);
+            let args = MoveDelegationArgs {
+                from_delegated_by_mapper: self.delegated_by(old_user_id, validator_id),
+                to_delegated_by_mapper: self.delegated_by(new_user_id, validator_id),
+                from_total_by_user_mapper: self.total_by_user(old_user_id, validator_id),
+                to_total_by_user_mapper: self.total_by_user(new_user_id, validator_id),
+                from_user_checkpoints_mapper: self
+                    .user_validator_stake_checkpoints(old_user_id, validator_id),
+                to_user_checkpoints_mapper: self
+                    .user_validator_stake_checkpoints(new_user_id, validator_id),
+                all_delegators_mapper: &mut self.all_delegators(validator_id),
+                from_user_targets_mapper: &mut self.validators_of_user(old_user_id),
+                to_user_targets_mapper: &mut self.validators_of_user(new_user_id),
+                from_user_id: old_user_id,
+                to_user_id: new_user_id,
+                target_id: validator_id,
+            };
+            self.move_delegation(args);
+            self.sync_validator_allocation_capacity(old_user_id, validator_id);
+            self.sync_validator_allocation_capacity(new_user_id, validator_id);
+        }
+
+        self.emit_validator_transfer_ownership_event(current_owner, caller);
+    }
+
+    #[endpoint(proposeSovOwner)]
+    fn propose_sov_owner(&self, new_owner: ManagedAddress) {
+        let caller = self.blockchain().get_caller();
+        let sov_id = self.resolve_sov_id_non_zero(&caller);
+        require!(new_owner != caller, "Already the owner");
+        require!(
+            self.resolve_sov_id(&new_owner) == NULL_ID,
+            "New owner is already a sovereign chain"
+        );
+
+        self.pending_sov_owner(sov_id).set(&new_owner);
+
+        self.emit_sov_propose_owner_event(caller, new_owner);
+    }
+
+    #[endpoint(acceptSovOwnership)]
+    fn accept_sov_ownership(&self, current_owner: ManagedAddress) {
+        let caller = self.blockchain().get_caller();
+        let sov_id = self.resolve_sov_id_non_zero(&current_owner);
+        self.require_pending_owner(&self.pending_sov_owner(sov_id), &caller);
+        require!(
+            self.resolve_sov_id(&caller) == NULL_ID,
+            "New owner is already a sovereign chain"
+        );
+
+        self.pending_sov_owner(sov_id).clear();
+        self.set_sov_owner(sov_id, &current_owner, &caller);
+
+        let old_user_id = self.user_ids().get_id(&current_owner);
+        if old_user_id != NULL_ID && !self.delegated_sov_by(old_user_id, sov_id).is_empty() {
+            let new_user_id = self.user_ids().get_id_or_insert(&caller);
+            let args = MoveDelegationArgs {
+                from_delegated_by_mapper: self.delegated_sov_by(old_user_id, sov_id),
+                to_delegated_by_mapper: self.delegated_sov_by(new_user_id, sov_id),
+                from_total_by_user_mapper: self.total_sov_by_user(old_user_id, sov_id),
+                to_total_by_user_mapper: self.total_sov_by_user(new_user_id, sov_id),
+                from_user_checkpoints_mapper: self.user_sov_stake_checkpoints(old_user_id, sov_id),
+                to_user_checkpoints_mapper: self.user_sov_stake_checkpoints(new_user_id, sov_id),
+                all_delegators_mapper: &mut self.all_sov_delegators(sov_id),
+                from_user_targets_mapper: &mut self.sovs_of_user(old_user_id),
+                to_user_targets_mapper: &mut self.sovs_of_user(new_user_id),
+                from_user_id: old_user_id,
+                to_user_id: new_user_id,
+                target_id: sov_id,
+            };
+            self.move_delegation(args);
+        }
+
+        self.emit_sov_transfer_ownership_event(current_owner, caller);
+    }
+
+    #[view(getPendingValidatorOwner)]
+    fn get_pending_validator_owner(
+        &self,
+        validator: ManagedAddress,
+    ) -> OptionalValue<ManagedAddress> {
+        let validator_id = self.resolve_validator_id(&validator);
+        let mapper = self.pending_validator_owner(validator_id);
+        if validator_id == NULL_ID || mapper.is_empty() {
+            return OptionalValue::None;
+        }
+
+        OptionalValue::Some(mapper.get())
+    }
+
+    #[view(getPendingSovOwner)]
+    fn get_pending_sov_owner(&self, sov_address: ManagedAddress) -> OptionalValue<ManagedAddress> {
+        let sov_id = self.resolve_sov_id(&sov_address);
+        let mapper = self.pending_sov_owner(sov_id);
+        if sov_id == NULL_ID || mapper.is_empty() {
+            return OptionalValue::None;
+        }
+
+        OptionalValue::Some(mapper.get())
+    }
+
+    fn require_pending_owner(
+        &self,
+        pending_owner_mapper: &SingleValueMapper<ManagedAddress>,
+        caller: &ManagedAddress,
+    ) {
+        require!(
+            !pending_owner_mapper.is_empty() && &pending_owner_mapper.get() == caller,
+            "Not the proposed owner"
+        );
+    }
+
+    fn move_delegation(&self, args: MoveDelegationArgs<Self::Api>) {
+        let moved_tokens = args.from_delegated_by_mapper.take();
+        let moved_total = args.from_total_by_user_mapper.take();
+        let _ = args.all_delegators_mapper.swap_remove(&args.from_user_id);
+        let _ = args.from_user_targets_mapper.swap_remove(&args.target_id);
+
+        let mut tokens = self.get_payments_or_default(&args.to_delegated_by_mapper);
+        for payment in &moved_tokens.into_payments() {
+            tokens.add_payment(payment);
+        }
+        args.to_delegated_by_mapper.set(tokens);
+        let new_total = args.to_total_by_user_mapper.update(|total| {
+            *total += &moved_total;
+
+            total.clone()
+        });
+        let previous_total = &new_total - &moved_total;
+        self.record_stake_checkpoint(
+            &args.from_user_checkpoints_mapper,
+            &moved_total,
+            BigUint::zero(),
+        );
+        self.record_stake_checkpoint(&args.to_user_checkpoints_mapper, &previous_total, new_total);
+        let _ = args.all_delegators_mapper.insert(args.to_user_id);
+        let _ = args.to_user_targets_mapper.insert(args.target_id);
+    }
+
+    #[storage_mapper("pendingValidatorOwner")]
+    fn pending_validator_owner(&self, validator_id: AddressId)
+        -> SingleValueMapper<ManagedAddress>;
+
+    #[storage_mapper("pendingSovOwner")]
+    fn pending_sov_owner(&self, sov_id: AddressId) -> SingleValueMapper<ManagedAddress>;
+}
@@ -0,0 +1,497 @@
+use crate::unique_payments::{PaymentsVec, UniquePayments};
+
+use super::{
+    common_actions::{AddDelegationArgs, RemoveDelegationArgs},
+    user::PaymentsMultiValue,
+};
+
+multiversx_sc::imports!();
+multiversx_sc::derive_imports!();
+
+pub const RECEIPT_TOKEN_DECIMALS: usize = 18;
+
+#[derive(TypeAbi, TopEncode, TopDecode, NestedEncode, NestedDecode, Clone, Copy, PartialEq)]
+pub enum ReceiptTargetType {
+    Validator,
+    Sovereign,
+}
+
+/// Informative only, the redeemable position is kept in the receiptPosition storage
+#[derive(TypeAbi, TopEncode, TopDecode, NestedEncode, NestedDecode)]
+pub struct ReceiptAttributes<M: ManagedTypeApi> {
+    pub target_type: ReceiptTargetType,
+    pub target: ManagedAddress<M>,
+    pub tokens: PaymentsVec<M>,
+    pub egld_value: BigUint<M>,
+}
+
+/// Position backing the receipts of a nonce.
+/// `receipt_supply` is the amount of receipts still in circulation.
+#[derive(TypeAbi, TopEncode, TopDecode, NestedEncode, NestedDecode)]
+pub struct ReceiptPosition<M: ManagedTypeApi> {
+    pub target_type: ReceiptTargetType,
+    pub target_id: AddressId,
+    pub tokens: UniquePayments<M>,
+    pub receipt_supply: BigUint<M>,
+}
+
+/// Tokens of the positions held for receipts, revoked when their target exited.
+/// Burning the receipts unbonds their share for the caller.
+#[derive(TypeAbi, TopEncode, TopDecode, NestedEncode, NestedDecode)]
+pub struct ReceiptUnbondingPool<M: ManagedTypeApi> {
+    pub target: ManagedAddress<M>,
+    pub tokens: UniquePayments<M>,
+}
+
+/// Delegated positions can be exchanged for a meta-ESDT receipt.
+/// While tokenized, the position is held by the contract itself, and whoever holds the receipt
+/// may either redeem it back into a delegation or burn it to revoke the position.
+#[multiversx_sc::module]
+pub trait ReceiptTokenModule:
+    super::redelegate::RedelegateModule
+    + super::user::UserModule
+    + crate::token_whitelist::TokenWhitelistModule
+    + super::validator::ValidatorModule
+    + super::self_bond::SelfBondModule
+    + super::sovereign::SovereignModule
+    + super::sov_allow_list::SovAllowListModule
+    + super::unbond::UnbondModule
+    + super::common_actions::CommonActionsModule
+    + super::common_storage::CommonStorageModule
+    + crate::events::user_events::UserEventsModule
+    + crate::events::validator_events::ValidatorEventsModule
+    + crate::events::sov_events::SovEventsModule
+    + utils::UtilsModule
+{
+    #[only_owner]
+    #[payable("EGLD")]
+    #[endpoint(issueReceiptToken)]
+    fn issue_receipt_token(&self, token_display_name: ManagedBuffer, token_ticker: ManagedBuffer) {
+        let issue_cost = self.call_value().egld_value().clone_value();
+        self.receipt_token().issue_and_set_all_roles(
+            EsdtTokenType::Meta,
+            issue_cost,
+            token_display_name,
+            token_ticker,
+            RECEIPT_TOKEN_DECIMALS,
+            None,
+        );
+    }
+
+    /// Pairs of (token_id, nonce, amount)
+    #[endpoint(tokenizeValidatorDelegation)]
+    fn tokenize_validator_delegation(
+        &self,
+        validator: ManagedAddress,
+        tokens: PaymentsMultiValue<Self::Api>,
+    ) -> EsdtTokenPayment {
+        self.require_non_empty_args(&tokens);
+
+        let caller = self.blockchain().get_caller();
+        let caller_id = self.user_ids().get_id_non_zero(&caller);
+        let validator_id = self.resolve_validator_id_non_zero(&validator);
+
+        let removed_payments = self
+            .remove_position(
+                ReceiptTargetType::Validator,
+                validator_id,
+                caller_id,
+                tokens,
+            )
+            .into_payments();
+        self.require_self_bond_after_revoke(validator_id, &validator, &caller);
+
+        self.tokenize_common(
+            caller,
+            ReceiptTargetType::Validator,
+            validator_id,
+            validator,
+            removed_payments,
+        )
+    }
+
+    /// Pairs of (token_id, nonce, amount)
+    #[endpoint(tokenizeSovDelegation)]
+    fn tokenize_sov_delegation(
+        &self,
+        sov_name: ManagedBuffer,
+        tokens: PaymentsMultiValue<Self::Api>,
+    ) -> EsdtTokenPayment {
+        self.require_non_empty_args(&tokens);
+
+        let caller = self.blockchain().get_caller();
+        let caller_id = self.user_ids().get_id_non_zero(&caller);
+        let sov_id = self.sov_chain_for_name(&sov_name).get();
+        self.require_valid_sov_id(sov_id);
+
+        let removed_payments = self
+            .remove_position(ReceiptTargetType::Sovereign, sov_id, caller_id, tokens)
+            .into_payments();
+
+        let sov_address = unsafe { self.get_sov_owner(sov_id).unwrap_unchecked() };
+        self.tokenize_common(
+            caller,
+            ReceiptTargetType::Sovereign,
+            sov_id,
+            sov_address,
+            removed_payments,
+        )
+    }
+
+    /// Burns the receipts and delegates their share of the position in the caller's name.
+    /// Checked like a new delegation to the target, and starts the redelegation cooldown there.
+    /// Redeemed stake is only recorded from the next epoch,
+    /// so it never counts towards the fee increase grace.
+    #[payable("*")]
+    #[endpoint(redeemReceipt)]
+    fn redeem_receipt(&self) {
+        let caller = self.blockchain().get_caller();
+        let caller_id = self.user_ids().get_id_or_insert(&caller);
+        let (target_type, target_id, payments) = self.burn_receipt_payment();
+        require!(
+            self.receipt_unbonding_pool(target_type, target_id)
+                .is_empty(),
+            "Receipt target exited, revoke instead"
+        );
+
+        let target = self.get_receipt_target_address(target_type, target_id);
+        let own_id = self.get_receipt_holder_id();
+        let moved_payments = self
+            .remove_position(
+                target_type,
+                target_id,
+                own_id,
+                self.to_payments_multi_value(payments),
+            )
+            .into_payments();
+        self.add_position(target_type, target_id, caller_id, moved_payments.clone());
+
+        match target_type {
+            ReceiptTargetType::Validator => {
+                self.require_validator_not_unregistering(target_id);
+                self.require_accepts_delegation(target_id, &target, &caller);
+                self.extend_redelegation_cooldown(
+                    self.validator_redelegation_cooldown_end(caller_id, target_id),
+                );
+            }
+            ReceiptTargetType::Sovereign => {
+                let total = self.get_whitelisted_payments_total(&moved_payments);
+                self.require_sov_accepts_delegation(target_id);
+                self.require_valid_sov_delegation(target_id, &moved_payments, &total);
+                self.require_allowed_for_sov(target_id, &caller);
+                self.require_user_within_max_per_delegator(target_id, caller_id);
+                self.extend_redelegation_cooldown(
+                    self.sov_redelegation_cooldown_end(caller_id, target_id),
+                );
+            }
+        }
+
+        self.emit_redeem_receipt_event(caller, target, moved_payments);
+    }
+
+    /// Burns the receipts and starts unbonding their share of the position for the caller
+    #[payable("*")]
+    #[endpoint(revokeWithReceipt)]
+    fn revoke_with_receipt(&self) {
+        let caller = self.blockchain().get_caller();
+        let caller_id = self.user_ids().get_id_or_insert(&caller);
+        let (target_type, target_id, payments) = self.burn_receipt_payment();
+
+        let pool_mapper = self.receipt_unbonding_pool(target_type, target_id);
+        let (target, output_unique_payments) = if pool_mapper.is_empty() {
+            let own_id = self.get_receipt_holder_id();
+            let output_unique_payments = self.remove_position(
+                target_type,
+                target_id,
+                own_id,
+                self.to_payments_multi_value(payments),
+            );
+
+            (
+                self.get_receipt_target_address(target_type, target_id),
+                output_unique_payments,
+            )
+        } else {
+            self.take_from_receipt_unbonding_pool(pool_mapper, payments)
+        };
+        self.add_unbond_tokens(caller_id, output_unique_payments.clone());
+
+        match target_type {
+            ReceiptTargetType::Validator => {
+                self.emit_revoke_validator_event(caller, target, output_unique_payments)
+            }
+            ReceiptTargetType::Sovereign => {
+                self.emit_revoke_sov_event(caller, target, output_unique_payments)
+            }
+        }
+    }
+
+    #[view(getReceiptPosition)]
+    fn get_receipt_position(&self, nonce: u64) -> OptionalValue<ReceiptPosition<Self::Api>> {
+        let position_mapper = self.receipt_position(nonce);
+        if position_mapper.is_empty() {
+            return OptionalValue::None;
+        }
+
+        OptionalValue::Some(position_mapper.get())
+    }
+
+    fn tokenize_common(
+        &self,
+        caller: ManagedAddress,
+        target_type: ReceiptTargetType,
+        target_id: AddressId,
+        target: ManagedAddress,
+        payments: PaymentsVec<Self::Api>,
+    ) -> EsdtTokenPayment {
+        let egld_value = self.get_whitelisted_payments_total(&payments);
+        require!(egld_value > 0, "Nothing to tokenize");
+        require!(
+            self.receipt_unbonding_pool(target_type, target_id)
+                .is_empty(),
+            "Receipt target exited"
+        );
+
+        let own_id = self.get_receipt_holder_id();
+        self.add_position(target_type, target_id, own_id, payments.clone());
+
+        let attributes = ReceiptAttributes {
+            target_type,
+            target: target.clone(),
+            tokens: payments.clone(),
+            egld_value: egld_value.clone(),
+        };
+        let receipt =
+            self.receipt_token()
+                .nft_create_and_send(&caller, egld_value.clone(), &attributes);
+        self.receipt_position(receipt.token_nonce)
+            .set(ReceiptPosition {
+                target_type,
+                target_id,
+                tokens: UniquePayments::new_from_payments(payments.clone()),
+                receipt_supply: egld_value,
+            });
+
+        self.emit_tokenize_delegation_event(
+            caller,
+            target,
+            receipt.token_nonce,
+            receipt.amount.clone(),
+            payments,
+        );
+
+        receipt
+    }
+
+    /// Returns the share of the position backing the burned receipts
+    fn burn_receipt_payment(&self) -> (ReceiptTargetType, AddressId, PaymentsVec<Self::Api>) {
+        let payment = self.call_value().single_esdt();
+        self.receipt_token()
+            .require_same_token(&payment.token_identifier);
+        require!(payment.amount > 0, "Invalid payment amount");
+
+        let position_mapper = self.receipt_position(payment.token_nonce);
+        let mut position = position_mapper.get();
+        let target_type = position.target_type;
+        let target_id = position.target_id;
+        let mut share = PaymentsVec::new();
+        if payment.amount == position.receipt_supply {
+            share = position.tokens.into_payments();
+            position_mapper.clear();
+        } else {
+            for token in &position.tokens.clone().into_payments() {
+                let amount = &token.amount * &payment.amount / &position.receipt_supply;
+                if amount == 0 {
+                    continue;
+                }
+
+                let token_share =
+                    EsdtTokenPayment::new(token.token_identifier, token.token_nonce, amount);
+                let deduct_result = position.tokens.deduct_payment(&token_share);
+                require!(deduct_result.is_ok(), "Invalid receipt position");
+
+                share.push(token_share);
+            }
+            require!(!share.is_empty(), "Receipt amount too low");
+
+            position.receipt_supply -= &payment.amount;
+            position_mapper.set(&position);
+        }
+
+        self.receipt_token()
+            .nft_burn(payment.token_nonce, &payment.amount);
+
+        (target_type, target_id, share)
+    }
+
+    fn remove_position(
+        &self,
+        target_type: ReceiptTargetType,
+        target_id: AddressId,
+        user_id: AddressId,
+        tokens: PaymentsMultiValue<Self::Api>,
+    ) -> UniquePayments<Self::Api> {
+        match target_type {
+            ReceiptTargetType::Validator => {
+                let output_payments = self.remove_delegation(RemoveDelegationArgs {
+                    total_delegated_mapper: self.total_delegated_amount(target_id),
+                    total_by_user_mapper: self.total_by_user(user_id, target_id),
+                    target_checkpoints_mapper: self.validator_stake_checkpoints(target_id),
+                    user_checkpoints_mapper: self
+                        .user_validator_stake_checkpoints(user_id, target_id),
+                    all_delegators_mapper: &mut self.all_delegators(target_id),
+                    user_targets_mapper: &mut self.validators_of_user(user_id),
+                    delegated_by_mapper: self.delegated_by(user_id, target_id),
+                    tokens,
+                    target_id,
+                    caller_id: user_id,
+                });
+                self.sync_validator_allocation_capacity(user_id, target_id);
+
+                output_payments
+            }
+            ReceiptTargetType::Sovereign => self.remove_delegation(RemoveDelegationArgs {
+                total_delegated_mapper: self.total_delegated_sov_amount(target_id),
+                total_by_user_mapper: self.total_sov_by_user(user_id, target_id),
+                target_checkpoints_mapper: self.sov_stake_checkpoints(target_id),
+                user_checkpoints_mapper: self.user_sov_stake_checkpoints(user_id, target_id),
+                all_delegators_mapper: &mut self.all_sov_delegators(target_id),
+                user_targets_mapper: &mut self.sovs_of_user(user_id),
+                delegated_by_mapper: self.delegated_sov_by(user_id, target_id),
+                tokens,
+                target_id,
+                caller_id: user_id,
+            }),
+        }
+    }
+
+    /// The position already counted towards the target's cap, so it's not checked again
+    fn add_position(
+        &self,
+        target_type: ReceiptTargetType,
+        target_id: AddressId,
+        user_id: AddressId,
+        payments: PaymentsVec<Self::Api>,
+    ) {
+        let total = self.get_whitelisted_payments_total(&payments);
+        match target_type {
+            ReceiptTargetType::Validator => {
+                self.add_delegation(AddDelegationArgs {
+                    total_delegated_mapper: self.total_delegated_amount(target_id),
+                    total_by_user_mapper: self.total_by_user(user_id, target_id),
+                    target_checkpoints_mapper: self.validator_stake_checkpoints(target_id),
+                    user_checkpoints_mapper: self
+                        .user_validator_stake_checkpoints(user_id, target_id),
+                    all_delegators_mapper: &mut self.all_delegators(target_id),
+                    user_targets_mapper: &mut self.validators_of_user(user_id),
+                    delegated_by_mapper: self.delegated_by(user_id, target_id),
+                    opt_max_delegation: None,
+                    payments_to_add: payments,
+                    total_amount: total,
+                    target_id,
+                    caller_id: user_id,
+                });
+                self.sync_validator_allocation_capacity(user_id, target_id);
+            }
+            ReceiptTargetType::Sovereign => self.add_delegation(AddDelegationArgs {
+                total_delegated_mapper: self.total_delegated_sov_amount(target_id),
+                total_by_user_mapper: self.total_sov_by_user(user_id, target_id),
+                target_checkpoints_mapper: self.sov_stake_checkpoints(target_id),
+                user_checkpoints_mapper: self.user_sov_stake_checkpoints(user_id, target_id),
+                all_delegators_mapper: &mut self.all_sov_delegators(target_id),
+                user_targets_mapper: &mut self.sovs_of_user(user_id),
+                delegated_by_mapper: self.delegated_sov_by(user_id, target_id),
+                opt_max_delegation: None,
+                payments_to_add: payments,
+                total_amount: total,
+                target_id,
+                caller_id: user_id,
+            }),
+        }
+    }
+
+    /// Tokenized positions are delegated in the name of the contract itself
+    fn get_receipt_holder_id(&self) -> AddressId {
+        let own_address = self.blockchain().get_sc_address();
+
+        self.user_ids().get_id_or_insert(&own_address)
+    }
+
+    fn is_receipt_holder(&self, user_id: AddressId) -> bool {
+        let own_address = self.blockchain().get_sc_address();
+
+        self.user_ids().get_id(&own_address) == user_id
+    }
+
+    /// Called instead of unbonding the revoked tokens when the target exits its delegators
+    fn add_to_receipt_unbonding_pool(
+        &self,
+        target_type: ReceiptTargetType,
+        target_id: AddressId,
+        target: &ManagedAddress,
+        tokens: UniquePayments<Self::Api>,
+    ) {
+        let pool_mapper = self.receipt_unbonding_pool(target_type, target_id);
+        let mut pool = if pool_mapper.is_empty() {
+            ReceiptUnbondingPool {
+                target: target.clone(),
+                tokens: UniquePayments::new(),
+            }
+        } else {
+            pool_mapper.get()
+        };
+        for payment in &tokens.into_payments() {
+            pool.tokens.add_payment(payment);
+        }
+
+        pool_mapper.set(pool);
+    }
+
+    fn take_from_receipt_unbonding_pool(
+        &self,
+        pool_mapper: SingleValueMapper<ReceiptUnbondingPool<Self::Api>>,
+        payments: PaymentsVec<Self::Api>,
+    ) -> (ManagedAddress, UniquePayments<Self::Api>) {
+        let mut pool = pool_mapper.get();
+        for payment in &payments {
+            let deduct_result = pool.tokens.deduct_payment(&payment);
+            require!(deduct_result.is_ok(), "Invalid receipt position");
+        }
+
+        if pool.tokens.is_empty() {
+            pool_mapper.clear();
+        } else {
+            pool_mapper.set(&pool);
+        }
+
+        (pool.target, UniquePayments::new_from_payments(payments))
+    }
+
+    fn get_receipt_target_address(
+        &self,
+        target_type: ReceiptTargetType,
+        target_id: AddressId,
+    ) -> ManagedAddress {
+        let opt_address = match target_type {
+            ReceiptTargetType::Validator => self.get_validator_owner(target_id),
+            ReceiptTargetType::Sovereign => self.get_sov_owner(target_id),
+        };
+        require!(opt_address.is_some(), "Receipt target not registered");
+
+        unsafe { opt_address.unwrap_unchecked() }
+    }
+
+    #[view(getReceiptTokenId)]
+    #[storage_mapper("receiptToken")]
+    fn receipt_token(&self) -> NonFungibleTokenMapper;
+
+    #[storage_mapper("receiptPosition")]
+    fn receipt_position(&self, nonce: u64) -> SingleValueMapper<ReceiptPosition<Self::Api>>;
+
+    #[storage_mapper("receiptUnbondingPool")]
+    fn receipt_unbonding_pool(
+        &self,
+        target_type: ReceiptTargetType,
+        target_id: AddressId,
+    ) -> SingleValueMapper<ReceiptUnbondingPool<Self::Api>>;
+}
@@ -0,0 +1,248 @@
+use super::{
+    common_actions::{AddDelegationArgs, RemoveDelegationArgs},
+    sovereign::Epoch,
+    user::PaymentsMultiValue,
+};
+
+multiversx_sc::imports!();
+multiversx_sc::derive_imports!();
+
+#[multiversx_sc::module]
+pub trait RedelegateModule:
+    super::user::UserModule
+    + crate::token_whitelist::TokenWhitelistModule
+    + super::validator::ValidatorModule
+    + super::self_bond::SelfBondModule
+    + super::sovereign::SovereignModule
+    + super::sov_allow_list::SovAllowListModule
+    + super::unbond::UnbondModule
+    + super::common_actions::CommonActionsModule
+    + super::common_storage::CommonStorageModule
+    + crate::events::user_events::UserEventsModule
+    + crate::events::validator_events::ValidatorEventsModule
+    + crate::events::sov_events::SovEventsModule
+    + utils::UtilsModule
+{
+    #[only_owner]
+    #[endpoint(setRedelegationCooldownEpochs)]
+    fn set_redelegation_cooldown_epochs(&self, cooldown_epochs: Epoch) {
+        self.redelegation_cooldown_epochs().set(cooldown_epochs);
+    }
+
+    /// Pairs of (token_id, nonce, amount)
+    #[endpoint(redelegateValidator)]
+    fn redelegate_validator(
+        &self,
+        from_validator: ManagedAddress,
+        to_validator: ManagedAddress,
+        tokens: PaymentsMultiValue<Self::Api>,
+    ) {
+        self.require_non_empty_args(&tokens);
+        require!(
+            from_validator != to_validator,
+            "Cannot redelegate to the same validator"
+        );
+
+        let caller = self.blockchain().get_caller();
+        let caller_id = self.user_ids().get_id_non_zero(&caller);
+        let from_validator_id = self.resolve_validator_id_non_zero(&from_validator);
+        let to_validator_id = self.resolve_validator_id_non_zero(&to_validator);
+        self.require_validator_not_unregistering(to_validator_id);
+
+        let to_validator_config = self.validator_config(to_validator_id).get();
+
+        let remove_args = RemoveDelegationArgs {
+            total_delegated_mapper: self.total_delegated_amount(from_validator_id),
+            total_by_user_mapper: self.total_by_user(caller_id, from_validator_id),
+            target_checkpoints_mapper: self.validator_stake_checkpoints(from_validator_id),
+            user_checkpoints_mapper: self
+                .user_validator_stake_checkpoints(caller_id, from_validator_id),
+            all_delegators_mapper: &mut self.all_delegators(from_validator_id),
+            user_targets_mapper: &mut self.validators_of_user(caller_id),
+            delegated_by_mapper: self.delegated_by(caller_id, from_validator_id),
+            tokens,
+            target_id: from_validator_id,
+            caller_id,
+        };
+        let moved_payments = self.remove_delegation(remove_args).into_payments();
+        self.sync_validator_allocation_capacity(caller_id, from_validator_id);
+        let total = self.get_whitelisted_payments_total(&moved_payments);
+
+        // the fee increase grace only waives the cooldown at the source
+        let to_cooldown_end_mapper =
+            self.validator_redelegation_cooldown_end(caller_id, to_validator_id);
+        if self.try_use_fee_grace(caller_id, from_validator_id, &total) {
+            self.extend_redelegation_cooldown(to_cooldown_end_mapper);
+        } else {
+            self.start_redelegation_cooldown(
+                self.validator_redelegation_cooldown_end(caller_id, from_validator_id),
+                to_cooldown_end_mapper,
+            );
+        }
+
+        let add_args = AddDelegationArgs {
+            total_delegated_mapper: self.total_delegated_amount(to_validator_id),
+            total_by_user_mapper: self.total_by_user(caller_id, to_validator_id),
+            target_checkpoints_mapper: self.validator_stake_checkpoints(to_validator_id),
+            user_checkpoints_mapper: self
+                .user_validator_stake_checkpoints(caller_id, to_validator_id),
+            all_delegators_mapper: &mut self.all_delegators(to_validator_id),
+            user_targets_mapper: &mut self.validators_of_user(caller_id),
+            delegated_by_mapper: self.delegated_by(caller_id, to_validator_id),
+            opt_max_delegation: to_validator_config.opt_max_delegation,
+            payments_to_add: moved_payments.clone(),
+            total_amount: total,
+            target_id: to_validator_id,
+            caller_id,
+        };
+        self.add_delegation(add_args);
+        self.sync_validator_allocation_capacity(caller_id, to_validator_id);
+        self.require_self_bond_after_revoke(from_validator_id, &from_validator, &caller);
+        self.require_accepts_delegation(to_validator_id, &to_validator, &caller);
+
+        self.emit_redelegate_validator_event(caller, from_validator, to_validator, moved_payments);
+    }
+
+    /// Pairs of (token_id, nonce, amount)
+    #[endpoint(redelegateSov)]
+    fn redelegate_sov(
+        &self,
+        from_sov_name: ManagedBuffer,
+        to_sov_name: ManagedBuffer,
+        tokens: PaymentsMultiValue<Self::Api>,
+    ) {
+        self.require_non_empty_args(&tokens);
+        require!(
+            from_sov_name != to_sov_name,
+            "Cannot redelegate to the same sovereign chain"
+        );
+
+        let caller = self.blockchain().get_caller();
+        let caller_id = self.user_ids().get_id_non_zero(&caller);
+        let from_sov_id = self.sov_chain_for_name(&from_sov_name).get();
+        self.require_valid_sov_id(from_sov_id);
+
+        let to_sov_id = self.sov_chain_for_name(&to_sov_name).get();
+        self.require_valid_sov_id(to_sov_id);
+        self.require_sov_accepts_delegation(to_sov_id);
+
+        let to_sov_info = self.sov_info(to_sov_id).get();
+        self.start_redelegation_cooldown(
+            self.sov_redelegation_cooldown_end(caller_id, from_sov_id),
+            self.sov_redelegation_cooldown_end(caller_id, to_sov_id),
+        );
+
+        let remove_args = RemoveDelegationArgs {
+            total_delegated_mapper: self.total_delegated_sov_amount(from_sov_id),
+            total_by_user_mapper: self.total_sov_by_user(caller_id, from_sov_id),
+            target_checkpoints_mapper: self.sov_stake_checkpoints(from_sov_id),
+            user_checkpoints_mapper: self.user_sov_stake_checkpoints(caller_id, from_sov_id),
+            all_delegators_mapper: &mut self.all_sov_delegators(from_sov_id),
+            user_targets_mapper: &mut self.sovs_of_user(caller_id),
+            delegated_by_mapper: self.delegated_sov_by(caller_id, from_sov_id),
+            tokens,
+            target_id: from_sov_id,
+            caller_id,
+        };
+        let moved_payments = self.remove_delegation(remove_args).into_payments();
+        let total = self.get_whitelisted_payments_total(&moved_payments);
+        self.require_valid_sov_delegation(to_sov_id, &moved_payments, &total);
+
+        let add_args = AddDelegationArgs {
+            total_delegated_mapper: self.total_delegated_sov_amount(to_sov_id),
+            total_by_user_mapper: self.total_sov_by_user(caller_id, to_sov_id),
+            target_checkpoints_mapper: self.sov_stake_checkpoints(to_sov_id),
+            user_checkpoints_mapper: self.user_sov_stake_checkpoints(caller_id, to_sov_id),
+            all_delegators_mapper: &mut self.all_sov_delegators(to_sov_id),
+            user_targets_mapper: &mut self.sovs_of_user(caller_id),
+            delegated_by_mapper: self.delegated_sov_by(caller_id, to_sov_id),
+            opt_max_delegation: to_sov_info.opt_max_restaking_cap,
+            payments_to_add: moved_payments.clone(),
+            total_amount: total,
+            target_id: to_sov_id,
+            caller_id,
+        };
+        self.add_delegation(add_args);
+        self.require_allowed_for_sov(to_sov_id, &caller);
+        self.require_user_within_max_per_delegator(to_sov_id, caller_id);
+
+        let from_sov_address = unsafe { self.get_sov_owner(from_sov_id).unwrap_unchecked() };
+        let to_sov_address = unsafe { self.get_sov_owner(to_sov_id).unwrap_unchecked() };
+        self.try_activate_sov(to_sov_id, &to_sov_address);
+
+        self.emit_redelegate_sov_event(caller, from_sov_address, to_sov_address, moved_payments);
+    }
+
+    #[view(getValidatorRedelegationCooldownEnd)]
+    fn get_validator_redelegation_cooldown_end(
+        &self,
+        user: ManagedAddress,
+        validator: ManagedAddress,
+    ) -> Epoch {
+        let user_id = self.user_ids().get_id(&user);
+        let validator_id = self.resolve_validator_id(&validator);
+        if user_id == NULL_ID || validator_id == NULL_ID {
+            return 0;
+        }
+
+        self.validator_redelegation_cooldown_end(user_id, validator_id)
+            .get()
+    }
+
+    #[view(getSovRedelegationCooldownEnd)]
+    fn get_sov_redelegation_cooldown_end(
+        &self,
+        user: ManagedAddress,
+        sov_address: ManagedAddress,
+    ) -> Epoch {
+        let user_id = self.user_ids().get_id(&user);
+        let sov_id = self.resolve_sov_id(&sov_address);
+        if user_id == NULL_ID || sov_id == NULL_ID {
+            return 0;
+        }
+
+        self.sov_redelegation_cooldown_end(user_id, sov_id).get()
+    }
+
+    /// Stake may only be redelegated away from a target once the cooldown started by
+    /// redelegating into it has passed, so it can't keep hopping between targets
+    fn start_redelegation_cooldown(
+        &self,
+        from_cooldown_end_mapper: SingleValueMapper<Epoch>,
+        to_cooldown_end_mapper: SingleValueMapper<Epoch>,
+    ) {
+        require!(
+            self.blockchain().get_block_epoch() >= from_cooldown_end_mapper.get(),
+            "Redelegation cooldown not finished"
+        );
+
+        self.extend_redelegation_cooldown(to_cooldown_end_mapper);
+    }
+
+    /// Stake moved into a target may not be redelegated away before the cooldown ends
+    fn extend_redelegation_cooldown(&self, cooldown_end_mapper: SingleValueMapper<Epoch>) {
+        let cooldown_end =
+            self.blockchain().get_block_epoch() + self.redelegation_cooldown_epochs().get();
+        if cooldown_end > cooldown_end_mapper.get() {
+            cooldown_end_mapper.set(cooldown_end);
+        }
+    }
+
+    #[view(getRedelegationCooldownEpochs)]
+    #[storage_mapper("redelegationCooldownEpochs")]
+    fn redelegation_cooldown_epochs(&self) -> SingleValueMapper<Epoch>;
+
+    #[storage_mapper("validatorRedelegationCooldownEnd")]
+    fn validator_redelegation_cooldown_end(
+        &self,
+        user_id: AddressId,
+        validator_id: AddressId,
+    ) -> SingleValueMapper<Epoch>;
+
+    #[storage_mapper("sovRedelegationCooldownEnd")]
+    fn sov_redelegation_cooldown_end(
+        &self,
+        user_id: AddressId,
+        sov_id: AddressId,
+    ) -> SingleValueMapper<Epoch>;
+}
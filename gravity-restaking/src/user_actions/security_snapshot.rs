@@ -0,0 +1,205 @@
+use super::{
+    operator_allocation::MAX_SOV_OPERATORS,
+    sovereign::Epoch,
+    validator::{BlsKey, BlsKeyStatus},
+};
+
+multiversx_sc::imports!();
+multiversx_sc::derive_imports!();
+
+/// Bumped on any change of the encoding, so receivers can reject unknown formats
+pub const SNAPSHOT_FORMAT_VERSION: u8 = 1;
+
+#[derive(TypeAbi, TopEncode, TopDecode, NestedEncode, NestedDecode, ManagedVecItem)]
+pub struct ValidatorSecurity<M: ManagedTypeApi> {
+    pub validator: ManagedAddress<M>,
+    pub stake: BigUint<M>,
+    pub bls_keys: ManagedVec<M, BlsKey<M>>,
+}
+
+#[derive(TypeAbi, TopEncode, TopDecode, NestedEncode, NestedDecode)]
+pub struct SecuritySnapshot<M: ManagedTypeApi> {
+    pub version: u8,
+    pub nonce: u64,
+    pub epoch: Epoch,
+    pub total_stake: BigUint<M>,
+    pub validators: ManagedVec<M, ValidatorSecurity<M>>,
+}
+
+mod security_bridge_proxy {
+    multiversx_sc::imports!();
+
+    #[multiversx_sc::proxy]
+    pub trait SecurityBridgeProxy {
+        #[endpoint(receiveSecuritySnapshot)]
+        fn receive_security_snapshot(&self, snapshot: super::SecuritySnapshot<Self::Api>);
+    }
+}
+
+/// Sends the security set of a sovereign chain to the bridge contract configured by the chain.
+/// The set is made of the operator allocations to the chain, along with the active BLS keys.
+/// Keys still kept in the legacy validator config are moved to the key registry before sending.
+#[multiversx_sc::module]
+pub trait SecuritySnapshotModule:
+    crate::token_whitelist::TokenWhitelistModule
+    + super::validator::ValidatorModule
+    + super::sovereign::SovereignModule
+    + super::operator_allocation::OperatorAllocationModule
+    + super::common_actions::CommonActionsModule
+    + super::common_storage::CommonStorageModule
+    + crate::events::user_events::UserEventsModule
+    + crate::events::validator_events::ValidatorEventsModule
+    + crate::events::sov_events::SovEventsModule
+    + utils::UtilsModule
+{
+    #[only_owner]
+    #[endpoint(setSnapshotIntervalEpochs)]
+    fn set_snapshot_interval_epochs(&self, interval_epochs: Epoch) {
+        self.snapshot_interval_epochs().set(interval_epochs);
+    }
+
+    #[endpoint(setSecurityBridge)]
+    fn set_security_bridge(&self, bridge: ManagedAddress) {
+        require!(
+            self.blockchain().is_smart_contract(&bridge),
+            "Invalid bridge address"
+        );
+
+        let caller = self.blockchain().get_caller();
+        let sov_id = self.resolve_sov_id_non_zero(&caller);
+        self.security_bridge(sov_id).set(&bridge);
+
+        self.emit_sov_set_security_bridge_event(caller, bridge);
+    }
+
+    /// The sovereign chain may send a snapshot at any time,
+    /// anyone else only once per snapshot interval
+    #[endpoint(sendSecuritySnapshot)]
+    fn send_security_snapshot(&self, sov_address: ManagedAddress) {
+        let sov_id = self.resolve_sov_id_non_zero(&sov_address);
+        let bridge_mapper = self.security_bridge(sov_id);
+        require!(!bridge_mapper.is_empty(), "No security bridge set");
+
+        let caller = self.blockchain().get_caller();
+        let current_epoch = self.blockchain().get_block_epoch();
+        let last_epoch_mapper = self.last_snapshot_epoch(sov_id);
+        if caller != sov_address && !last_epoch_mapper.is_empty() {
+            let next_epoch = last_epoch_mapper.get() + self.snapshot_interval_epochs().get();
+            require!(current_epoch >= next_epoch, "Snapshot interval not passed");
+        }
+
+        let nonce = self.last_snapshot_nonce(sov_id).update(|nonce| {
+            *nonce += 1;
+
+            *nonce
+        });
+        last_epoch_mapper.set(current_epoch);
+
+        for validator_id in self
+            .all_sov_operators(sov_id)
+            .iter()
+            .take(MAX_SOV_OPERATORS)
+        {
+            self.move_legacy_bls_keys(validator_id);
+        }
+
+        let snapshot = self.build_security_snapshot(sov_id, nonce, current_epoch);
+        self.emit_sov_security_snapshot_event(
+            sov_address,
+            nonce,
+            current_epoch,
+            snapshot.total_stake.clone(),
+        );
+
+        self.security_bridge_proxy_obj(bridge_mapper.get())
+            .receive_security_snapshot(snapshot)
+            .transfer_execute();
+    }
+
+    #[view(getSecuritySnapshot)]
+    fn get_security_snapshot(
+        &self,
+        sov_address: ManagedAddress,
+    ) -> OptionalValue<SecuritySnapshot<Self::Api>> {
+        let sov_id = self.resolve_sov_id(&sov_address);
+        if sov_id == NULL_ID {
+            return OptionalValue::None;
+        }
+
+        let nonce = self.last_snapshot_nonce(sov_id).get() + 1;
+        let current_epoch = self.blockchain().get_block_epoch();
+
+        OptionalValue::Some(self.build_security_snapshot(sov_id, nonce, current_epoch))
+    }
+
+    #[view(getSecurityBridge)]
+    fn get_security_bridge(&self, sov_address: ManagedAddress) -> OptionalValue<ManagedAddress> {
+        let sov_id = self.resolve_sov_id(&sov_address);
+        if sov_id == NULL_ID || self.security_bridge(sov_id).is_empty() {
+            return OptionalValue::None;
+        }
+
+        OptionalValue::Some(self.security_bridge(sov_id).get())
+    }
+
+    fn build_security_snapshot(
+        &self,
+        sov_id: AddressId,
+        nonce: u64,
+        epoch: Epoch,
+    ) -> SecuritySnapshot<Self::Api> {
+        let mut total_stake = BigUint::zero();
+        let mut validators = ManagedVec::new();
+        for validator_id in self
+            .all_sov_operators(sov_id)
+            .iter()
+            .take(MAX_SOV_OPERATORS)
+        {
+            let mut bls_keys = ManagedVec::new();
+            for bls_key in self.bls_keys(validator_id).iter() {
+                if self.bls_key_status(&bls_key).get() == BlsKeyStatus::Active
+                    && !self.bls_key_jailed_for_sov(&bls_key).contains(&sov_id)
+                {
+                    bls_keys.push(bls_key);
+                }
+            }
+
+            let stake = self.operator_allocation(validator_id, sov_id).get();
+            total_stake += &stake;
+
+            let validator = unsafe { self.get_validator_owner(validator_id).unwrap_unchecked() };
+            validators.push(ValidatorSecurity {
+                validator,
+                stake,
+                bls_keys,
+            });
+        }
+
+        SecuritySnapshot {
+            version: SNAPSHOT_FORMAT_VERSION,
+            nonce,
+            epoch,
+            total_stake,
+            validators,
+        }
+    }
+
+    #[proxy]
+    fn security_bridge_proxy_obj(
+        &self,
+        sc_address: ManagedAddress,
+    ) -> security_bridge_proxy::Proxy<Self::Api>;
+
+    #[view(getSnapshotIntervalEpochs)]
+    #[storage_mapper("snapshotIntervalEpochs")]
+    fn snapshot_interval_epochs(&self) -> SingleValueMapper<Epoch>;
+
+    #[storage_mapper("securityBridge")]
+    fn security_bridge(&self, sov_id: AddressId) -> SingleValueMapper<ManagedAddress>;
+
+    #[storage_mapper("lastSnapshotNonce")]
+    fn last_snapshot_nonce(&self, sov_id: AddressId) -> SingleValueMapper<u64>;
+
+    #[storage_mapper("lastSnapshotEpoch")]
+    fn last_snapshot_epoch(&self, sov_id: AddressId) -> SingleValueMapper<Epoch>;
+}
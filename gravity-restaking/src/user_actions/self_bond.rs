@@ -0,0 +1,124 @@
+use super::validator::{Percent, MAX_PERCENT};
+
+multiversx_sc::imports!();
+
+/// The self-bond of a validator is what it delegated to itself through its own user ID.
+/// Both the absolute and the ratio requirement have to be met.
+#[multiversx_sc::module]
+pub trait SelfBondModule:
+    crate::token_whitelist::TokenWhitelistModule
+    + super::validator::ValidatorModule
+    + super::common_actions::CommonActionsModule
+    + super::common_storage::CommonStorageModule
+    + crate::events::validator_events::ValidatorEventsModule
+    + utils::UtilsModule
+{
+    #[only_owner]
+    #[endpoint(setMinSelfBond)]
+    fn set_min_self_bond(&self, min_self_bond: BigUint) {
+        self.min_self_bond().set(min_self_bond);
+    }
+
+    /// Percent of the validator's total delegated amount
+    #[only_owner]
+    #[endpoint(setMinSelfBondPercent)]
+    fn set_min_self_bond_percent(&self, min_percent: Percent) {
+        require!(min_percent <= MAX_PERCENT, "Invalid percent");
+
+        self.min_self_bond_percent().set(min_percent);
+    }
+
+    #[view(getSelfBond)]
+    fn get_self_bond(&self, validator: ManagedAddress) -> BigUint {
+        let validator_id = self.resolve_validator_id(&validator);
+        if validator_id == NULL_ID {
+            return BigUint::zero();
+        }
+
+        self.get_self_bond_by_id(validator_id, &validator)
+    }
+
+    #[view(isSelfBondCompliant)]
+    fn is_self_bond_compliant(&self, validator: ManagedAddress) -> bool {
+        let validator_id = self.resolve_validator_id(&validator);
+        if validator_id == NULL_ID {
+            return false;
+        }
+
+        self.is_self_bond_compliant_by_id(validator_id, &validator)
+    }
+
+    fn get_self_bond_by_id(&self, validator_id: AddressId, validator: &ManagedAddress) -> BigUint {
+        let user_id_of_validator = self.user_ids().get_id(validator);
+        if user_id_of_validator == NULL_ID {
+            return BigUint::zero();
+        }
+
+        self.total_by_user(user_id_of_validator, validator_id).get()
+    }
+
+    fn is_self_bond_compliant_by_id(
+        &self,
+        validator_id: AddressId,
+        validator: &ManagedAddress,
+    ) -> bool {
+        let self_bond = self.get_self_bond_by_id(validator_id, validator);
+        if self_bond < self.min_self_bond().get() {
+            return false;
+        }
+
+        let total_delegated = self.total_delegated_amount(validator_id).get();
+        let min_percent = self.min_self_bond_percent().get();
+
+        self_bond * MAX_PERCENT >= total_delegated * min_percent
+    }
+
+    /// To be called after the delegation was added.
+    /// The validator itself may always add to its self-bond.
+    fn require_accepts_delegation(
+        &self,
+        validator_id: AddressId,
+        validator: &ManagedAddress,
+        delegator: &ManagedAddress,
+    ) {
+        if validator == delegator {
+            return;
+        }
+
+        require!(
+            self.is_self_bond_compliant_by_id(validator_id, validator),
+            "Validator self-bond too low"
+        );
+    }
+
+    /// To be called after the delegation was removed.
+    /// The validator may leave freely once it's the only delegator left, or while unregistering.
+    fn require_self_bond_after_revoke(
+        &self,
+        validator_id: AddressId,
+        validator: &ManagedAddress,
+        delegator: &ManagedAddress,
+    ) {
+        if validator != delegator || self.validator_unregistering(validator_id).get() {
+            return;
+        }
+
+        let self_bond = self.get_self_bond_by_id(validator_id, validator);
+        if self_bond == self.total_delegated_amount(validator_id).get() {
+            return;
+        }
+
+        require!(
+            self.is_self_bond_compliant_by_id(validator_id, validator),
+            "Cannot revoke below the minimum self-bond"
+        );
+    }
+
+    #[view(getMinSelfBond)]
+    #[storage_mapper("minSelfBond")]
+    fn min_self_bond(&self) -> SingleValueMapper<BigUint>;
+
+    #[view(getMinSelfBondPercent)]
+    #[storage_mapper("minSelfBondPercent")]
+    fn min_self_bond_percent(&self) -> SingleValueMapper<Percent>;
+}
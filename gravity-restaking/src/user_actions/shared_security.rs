@@ -0,0 +1,417 @@
+use crate::unique_payments::{PaymentsVec, UniquePayments};
+
+use super::{
+    common_actions::{AddDelegationArgs, RemoveDelegationArgs, StakeCheckpoint},
+    sovereign::Epoch,
+    user::PaymentsMultiValue,
+    validator::{Percent, MAX_PERCENT},
+};
+
+multiversx_sc::imports!();
+multiversx_sc::derive_imports!();
+
+pub const MAX_SHARED_SECURITY_MULTIPLIER: Percent = 50_000;
+
+#[derive(TypeAbi, TopEncode, TopDecode, NestedEncode, NestedDecode, ManagedVecItem)]
+pub struct PendingDeallocation<M: ManagedTypeApi> {
+    pub sov_id: AddressId,
+    pub tokens: UniquePayments<M>,
+    pub release_epoch: Epoch,
+}
+
+/// Shared security lets the same locked stake back multiple sovereign chains.
+///
+/// A single allocation can never exceed the locked amount of that token,
+/// while the sum of all allocations is capped at the locked amount times the multiplier.
+/// Deallocating goes through the unbond period: the sovereign chain stops counting the stake,
+/// but it keeps covering the allocation until released.
+/// Unlocking goes through the unbond period as well,
+/// and is only allowed while the remaining stake still covers all allocations.
+#[multiversx_sc::module]
+pub trait SharedSecurityModule:
+    super::user::UserModule
+    + crate::token_whitelist::TokenWhitelistModule
+    + super::validator::ValidatorModule
+    + super::sovereign::SovereignModule
+    + super::sov_allow_list::SovAllowListModule
+    + super::unbond::UnbondModule
+    + super::common_actions::CommonActionsModule
+    + super::common_storage::CommonStorageModule
+    + super::delegation_rewards::DelegationRewardsModule
+    + crate::events::user_events::UserEventsModule
+    + crate::events::validator_events::ValidatorEventsModule
+    + crate::events::sov_events::SovEventsModule
+    + utils::UtilsModule
+{
+    /// MAX_PERCENT means 1x, i.e. no overlapping allocations
+    #[only_owner]
+    #[endpoint(setSharedSecurityMultiplier)]
+    fn set_shared_security_multiplier(&self, multiplier: Percent) {
+        require!(
+            (MAX_PERCENT..=MAX_SHARED_SECURITY_MULTIPLIER).contains(&multiplier),
+            "Invalid multiplier"
+        );
+
+        self.shared_security_multiplier().set(multiplier);
+    }
+
+    /// Pairs of (token_id, nonce, amount)
+    #[endpoint(lockSharedStake)]
+    fn lock_shared_stake(&self, tokens: PaymentsMultiValue<Self::Api>) {
+        self.require_non_empty_args(&tokens);
+        self.require_shared_security_enabled();
+
+        let caller = self.blockchain().get_caller();
+        let caller_id = self.user_ids().get_id_non_zero(&caller);
+        let (output_payments, _) = self.before_add_delegation(self.user_tokens(caller_id), tokens);
+
+        let shared_stake_mapper = self.shared_stake(caller_id);
+        let mut shared_stake = self.get_payments_or_default(&shared_stake_mapper);
+        for payment in &output_payments {
+            shared_stake.add_payment(payment);
+        }
+        shared_stake_mapper.set(shared_stake);
+
+        self.emit_lock_shared_stake_event(caller, output_payments);
+    }
+
+    /// Pairs of (token_id, nonce, amount)
+    #[endpoint(unlockSharedStake)]
+    fn unlock_shared_stake(&self, tokens: PaymentsMultiValue<Self::Api>) {
+        self.require_non_empty_args(&tokens);
+
+        let caller = self.blockchain().get_caller();
+        let caller_id = self.user_ids().get_id_non_zero(&caller);
+        let shared_stake_mapper = self.shared_stake(caller_id);
+        require!(!shared_stake_mapper.is_empty(), "Nothing locked");
+
+        self.release_shared_deallocations(caller_id);
+
+        let mut output_payments = PaymentsVec::new();
+        shared_stake_mapper.update(|shared_stake| {
+            for token_tuple in tokens {
+                let (token_id, nonce, amount) = token_tuple.into_tuple();
+                require!(amount > 0, "Can't unlock 0");
+
+                let payment = EsdtTokenPayment::new(token_id, nonce, amount);
+                let deduct_result = shared_stake.deduct_payment(&payment);
+                require!(deduct_result.is_ok(), "Trying to unlock too many tokens");
+
+                output_payments.push(payment);
+            }
+        });
+
+        self.require_within_shared_multiplier(caller_id, &output_payments);
+        for sov_id in self.shared_sovs_of_user(caller_id).iter() {
+            self.require_sov_allocation_covered(caller_id, sov_id, &output_payments);
+        }
+        for pending_deallocation in &self.get_pending_deallocations(caller_id) {
+            self.require_sov_allocation_covered(
+                caller_id,
+                pending_deallocation.sov_id,
+                &output_payments,
+            );
+        }
+
+        let output_unique_payments = UniquePayments::new_from_payments(output_payments.clone());
+        self.add_unbond_tokens(caller_id, output_unique_payments);
+
+        self.emit_unlock_shared_stake_event(caller, output_payments);
+    }
+
+    /// Pairs of (token_id, nonce, amount)
+    #[endpoint(allocateSharedStake)]
+    fn allocate_shared_stake(
+        &self,
+        sov_name: ManagedBuffer,
+        tokens: PaymentsMultiValue<Self::Api>,
+    ) {
+        self.require_non_empty_args(&tokens);
+        self.require_shared_security_enabled();
+
+        let caller = self.blockchain().get_caller();
+        let caller_id = self.user_ids().get_id_non_zero(&caller);
+        let sov_id = self.sov_chain_for_name(&sov_name).get();
+        self.require_valid_sov_id(sov_id);
+        self.require_sov_accepts_delegation(sov_id);
+        self.release_shared_deallocations(caller_id);
+
+        let mut payments = PaymentsVec::new();
+        for token_tuple in tokens {
+            let (token_id, nonce, amount) = token_tuple.into_tuple();
+            require!(amount > 0, "Can't allocate 0");
+
+            payments.push(EsdtTokenPayment::new(token_id, nonce, amount));
+        }
+
+        let sov_info = self.sov_info(sov_id).get();
+        let total = self.get_whitelisted_payments_total(&payments);
+        self.require_valid_sov_delegation(sov_id, &payments, &total);
+        let args = AddDelegationArgs {
+            total_delegated_mapper: self.total_delegated_sov_amount(sov_id),
+            total_by_user_mapper: self.total_shared_sov_by_user(caller_id, sov_id),
+            target_checkpoints_mapper: self.sov_stake_checkpoints(sov_id),
+            user_checkpoints_mapper: self.user_shared_sov_stake_checkpoints(caller_id, sov_id),
+            all_delegators_mapper: &mut self.all_shared_sov_delegators(sov_id),
+            user_targets_mapper: &mut self.shared_sovs_of_user(caller_id),
+            delegated_by_mapper: self.shared_sov_allocation(caller_id, sov_id),
+            opt_max_delegation: sov_info.opt_max_restaking_cap,
+            payments_to_add: payments.clone(),
+            total_amount: total,
+            target_id: sov_id,
+            caller_id,
+        };
+        self.add_delegation(args);
+
+        let total_allocated_mapper = self.total_shared_allocated(caller_id);
+        let mut total_allocated = self.get_payments_or_default(&total_allocated_mapper);
+        for payment in &payments {
+            total_allocated.add_payment(payment);
+        }
+        total_allocated_mapper.set(total_allocated);
+
+        self.require_within_shared_multiplier(caller_id, &payments);
+        self.require_sov_allocation_covered(caller_id, sov_id, &payments);
+        self.require_allowed_for_sov(sov_id, &caller);
+        self.require_user_within_max_per_delegator(sov_id, caller_id);
+
+        let sov_address = unsafe { self.get_sov_owner(sov_id).unwrap_unchecked() };
+        self.try_activate_sov(sov_id, &sov_address);
+
+        self.emit_allocate_shared_stake_event(caller, sov_address, payments);
+    }
+
+    /// Pairs of (token_id, nonce, amount).
+    /// The tokens are released from the allocation after the unbond period.
+    #[endpoint(deallocateSharedStake)]
+    fn deallocate_shared_stake(
+        &self,
+        sov_name: ManagedBuffer,
+        tokens: PaymentsMultiValue<Self::Api>,
+    ) {
+        self.require_non_empty_args(&tokens);
+
+        let caller = self.blockchain().get_caller();
+        let caller_id = self.user_ids().get_id_non_zero(&caller);
+        let sov_id = self.sov_chain_for_name(&sov_name).get();
+        self.require_valid_sov_id(sov_id);
+
+        let args = RemoveDelegationArgs {
+            total_delegated_mapper: self.total_delegated_sov_amount(sov_id),
+            total_by_user_mapper: self.total_shared_sov_by_user(caller_id, sov_id),
+            target_checkpoints_mapper: self.sov_stake_checkpoints(sov_id),
+            user_checkpoints_mapper: self.user_shared_sov_stake_checkpoints(caller_id, sov_id),
+            all_delegators_mapper: &mut self.all_shared_sov_delegators(sov_id),
+            user_targets_mapper: &mut self.shared_sovs_of_user(caller_id),
+            delegated_by_mapper: self.shared_sov_allocation(caller_id, sov_id),
+            tokens,
+            target_id: sov_id,
+            caller_id,
+        };
+        let output_payments = self.remove_delegation(args).into_payments();
+        self.release_shared_deallocations(caller_id);
+        self.start_shared_deallocation(caller_id, sov_id, &output_payments);
+
+        let sov_address = unsafe { self.get_sov_owner(sov_id).unwrap_unchecked() };
+        self.emit_deallocate_shared_stake_event(caller, sov_address, output_payments);
+    }
+
+    #[view(getSharedStake)]
+    fn get_shared_stake(&self, user: ManagedAddress) -> UniquePayments<Self::Api> {
+        let user_id = self.user_ids().get_id(&user);
+        if user_id == NULL_ID {
+            return UniquePayments::new();
+        }
+
+        self.get_payments_or_default(&self.shared_stake(user_id))
+    }
+
+    #[view(getSharedAllocation)]
+    fn get_shared_allocation(
+        &self,
+        user: ManagedAddress,
+        sov_address: ManagedAddress,
+    ) -> UniquePayments<Self::Api> {
+        let user_id = self.user_ids().get_id(&user);
+        let sov_id = self.resolve_sov_id(&sov_address);
+        if user_id == NULL_ID || sov_id == NULL_ID {
+            return UniquePayments::new();
+        }
+
+        self.get_payments_or_default(&self.shared_sov_allocation(user_id, sov_id))
+    }
+
+    #[view(getPendingSharedDeallocations)]
+    fn get_pending_shared_deallocations(
+        &self,
+        user: ManagedAddress,
+    ) -> ManagedVec<PendingDeallocation<Self::Api>> {
+        let user_id = self.user_ids().get_id(&user);
+        if user_id == NULL_ID {
+            return ManagedVec::new();
+        }
+
+        self.get_pending_deallocations(user_id)
+    }
+
+    /// The tokens keep counting towards the user's allocations until the release epoch
+    fn start_shared_deallocation(
+        &self,
+        user_id: AddressId,
+        sov_id: AddressId,
+        payments: &PaymentsVec<Self::Api>,
+    ) {
+        let release_epoch = self.blockchain().get_block_epoch() + self.unbond_epochs().get();
+        let pending_deallocation = PendingDeallocation {
+            sov_id,
+            tokens: UniquePayments::new_from_payments(payments.clone()),
+            release_epoch,
+        };
+
+        let mut pending_deallocations = self.get_pending_deallocations(user_id);
+        pending_deallocations.push(pending_deallocation);
+        self.pending_shared_deallocations(user_id)
+            .set(pending_deallocations);
+    }
+
+    fn release_shared_deallocations(&self, user_id: AddressId) {
+        let pending_mapper = self.pending_shared_deallocations(user_id);
+        if pending_mapper.is_empty() {
+            return;
+        }
+
+        let current_epoch = self.blockchain().get_block_epoch();
+        let mut still_pending = ManagedVec::new();
+        let mut released = PaymentsVec::new();
+        for pending_deallocation in &pending_mapper.get() {
+            if pending_deallocation.release_epoch > current_epoch {
+                still_pending.push(pending_deallocation);
+
+                continue;
+            }
+
+            released.append_vec(pending_deallocation.tokens.into_payments());
+        }
+
+        if released.is_empty() {
+            return;
+        }
+
+        self.total_shared_allocated(user_id)
+            .update(|total_allocated| {
+                for payment in &released {
+                    let _ = total_allocated.deduct_payment(&payment);
+                }
+            });
+
+        if still_pending.is_empty() {
+            pending_mapper.clear();
+        } else {
+            pending_mapper.set(still_pending);
+        }
+    }
+
+    fn get_pending_deallocations(
+        &self,
+        user_id: AddressId,
+    ) -> ManagedVec<PendingDeallocation<Self::Api>> {
+        let pending_mapper = self.pending_shared_deallocations(user_id);
+        if pending_mapper.is_empty() {
+            return ManagedVec::new();
+        }
+
+        pending_mapper.get()
+    }
+
+    fn require_shared_security_enabled(&self) {
+        require!(
+            !self.shared_security_multiplier().is_empty(),
+            "Shared security not enabled"
+        );
+    }
+
+    fn require_within_shared_multiplier(
+        &self,
+        user_id: AddressId,
+        tokens: &PaymentsVec<Self::Api>,
+    ) {
+        let multiplier = self.shared_security_multiplier().get();
+        let shared_stake = self.get_payments_or_default(&self.shared_stake(user_id));
+        let total_allocated = self.get_payments_or_default(&self.total_shared_allocated(user_id));
+        for payment in tokens {
+            let locked = shared_stake.get_amount(&payment.token_identifier, payment.token_nonce);
+            let allocated =
+                total_allocated.get_amount(&payment.token_identifier, payment.token_nonce);
+            require!(
+                allocated * MAX_PERCENT <= locked * multiplier,
+                "Shared security multiplier exceeded"
+            );
+        }
+    }
+
+    fn require_sov_allocation_covered(
+        &self,
+        user_id: AddressId,
+        sov_id: AddressId,
+        tokens: &PaymentsVec<Self::Api>,
+    ) {
+        let shared_stake = self.get_payments_or_default(&self.shared_stake(user_id));
+        let mut sov_allocation =
+            self.get_payments_or_default(&self.shared_sov_allocation(user_id, sov_id));
+        for pending_deallocation in &self.get_pending_deallocations(user_id) {
+            if pending_deallocation.sov_id != sov_id {
+                continue;
+            }
+
+            for payment in &pending_deallocation.tokens.into_payments() {
+                sov_allocation.add_payment(payment);
+            }
+        }
+
+        for payment in tokens {
+            let locked = shared_stake.get_amount(&payment.token_identifier, payment.token_nonce);
+            let allocated =
+                sov_allocation.get_amount(&payment.token_identifier, payment.token_nonce);
+            require!(allocated <= locked, "Allocation exceeds locked stake");
+        }
+    }
+
+    #[view(getSharedSecurityMultiplier)]
+    #[storage_mapper("sharedSecurityMultiplier")]
+    fn shared_security_multiplier(&self) -> SingleValueMapper<Percent>;
+
+    #[storage_mapper("sharedStake")]
+    fn shared_stake(&self, user_id: AddressId) -> SingleValueMapper<UniquePayments<Self::Api>>;
+
+    #[storage_mapper("totalSharedAllocated")]
+    fn total_shared_allocated(
+        &self,
+        user_id: AddressId,
+    ) -> SingleValueMapper<UniquePayments<Self::Api>>;
+
+    #[storage_mapper("pendingSharedDeallocations")]
+    fn pending_shared_deallocations(
+        &self,
+        user_id: AddressId,
+    ) -> SingleValueMapper<ManagedVec<PendingDeallocation<Self::Api>>>;
+
+    #[storage_mapper("sharedSovsOfUser")]
+    fn shared_sovs_of_user(&self, user_id: AddressId) -> UnorderedSetMapper<AddressId>;
+
+    #[storage_mapper("allSharedSovDelegators")]
+    fn all_shared_sov_delegators(&self, sov_id: AddressId) -> UnorderedSetMapper<AddressId>;
+
+    #[storage_mapper("sharedSovAllocation")]
+    fn shared_sov_allocation(
+        &self,
+        user_id: AddressId,
+        sov_id: AddressId,
+    ) -> SingleValueMapper<UniquePayments<Self::Api>>;
+
+    #[storage_mapper("userSharedSovStakeCheckpoints")]
+    fn user_shared_sov_stake_checkpoints(
+        &self,
+        user_id: AddressId,
+        sov_id: AddressId,
+    ) -> VecMapper<StakeCheckpoint<Self::Api>>;
+}
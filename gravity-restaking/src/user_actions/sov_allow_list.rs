@@ -0,0 +1,135 @@
+multiversx_sc::imports!();
+
+/// Permissioned sovereign chains only accept restakers and validators from their allow-list
+#[multiversx_sc::module]
+pub trait SovAllowListModule:
+    crate::token_whitelist::TokenWhitelistModule
+    + super::sovereign::SovereignModule
+    + super::common_actions::CommonActionsModule
+    + super::common_storage::CommonStorageModule
+    + crate::events::sov_events::SovEventsModule
+    + utils::UtilsModule
+{
+    #[endpoint(setAllowListEnabled)]
+    fn set_allow_list_enabled(&self, enabled: bool) {
+        let caller = self.blockchain().get_caller();
+        let sov_id = self.resolve_sov_id_non_zero(&caller);
+        self.sov_allow_list_enabled(sov_id).set(enabled);
+
+        self.emit_sov_set_allow_list_enabled_event(caller, enabled);
+    }
+
+    #[endpoint(addToAllowList)]
+    fn add_to_allow_list(&self, addresses: MultiValueEncoded<ManagedAddress>) {
+        let caller = self.blockchain().get_caller();
+        let sov_id = self.resolve_sov_id_non_zero(&caller);
+
+        let mut allow_list_mapper = self.sov_allow_list(sov_id);
+        let mut added_addresses = ManagedVec::new();
+        for address in addresses {
+            if allow_list_mapper.insert(address.clone()) {
+                added_addresses.push(address);
+            }
+        }
+
+        self.emit_sov_add_to_allow_list_event(caller, added_addresses);
+    }
+
+    /// Existing delegations of removed addresses are kept, but can't be increased
+    #[endpoint(removeFromAllowList)]
+    fn remove_from_allow_list(&self, addresses: MultiValueEncoded<ManagedAddress>) {
+        let caller = self.blockchain().get_caller();
+        let sov_id = self.resolve_sov_id_non_zero(&caller);
+
+        let mut allow_list_mapper = self.sov_allow_list(sov_id);
+        let mut removed_addresses = ManagedVec::new();
+        for address in addresses {
+            if allow_list_mapper.swap_remove(&address) {
+                removed_addresses.push(address);
+            }
+        }
+
+        self.emit_sov_remove_from_allow_list_event(caller, removed_addresses);
+    }
+
+    /// Maximum value delegated by a single delegator. 0 means no limit.
+    #[endpoint(setMaxPerDelegator)]
+    fn set_max_per_delegator(&self, max_per_delegator: BigUint) {
+        let caller = self.blockchain().get_caller();
+        let sov_id = self.resolve_sov_id_non_zero(&caller);
+        self.sov_max_per_delegator(sov_id).set(&max_per_delegator);
+
+        self.emit_sov_set_max_per_delegator_event(caller, max_per_delegator);
+    }
+
+    #[view(isAllowListEnabled)]
+    fn is_allow_list_enabled(&self, sov_address: ManagedAddress) -> bool {
+        let sov_id = self.resolve_sov_id(&sov_address);
+        if sov_id == NULL_ID {
+            return false;
+        }
+
+        self.sov_allow_list_enabled(sov_id).get()
+    }
+
+    #[view(getSovAllowList)]
+    fn get_sov_allow_list(&self, sov_address: ManagedAddress) -> MultiValueEncoded<ManagedAddress> {
+        let mut result = MultiValueEncoded::new();
+        let sov_id = self.resolve_sov_id(&sov_address);
+        if sov_id == NULL_ID {
+            return result;
+        }
+
+        for address in self.sov_allow_list(sov_id).iter() {
+            result.push(address);
+        }
+
+        result
+    }
+
+    #[view(getMaxPerDelegator)]
+    fn get_max_per_delegator(&self, sov_address: ManagedAddress) -> BigUint {
+        let sov_id = self.resolve_sov_id(&sov_address);
+        if sov_id == NULL_ID {
+            return BigUint::zero();
+        }
+
+        self.sov_max_per_delegator(sov_id).get()
+    }
+
+    fn require_allowed_for_sov(&self, sov_id: AddressId, address: &ManagedAddress) {
+        if !self.sov_allow_list_enabled(sov_id).get() {
+            return;
+        }
+
+        require!(
+            self.sov_allow_list(sov_id).contains(address),
+            "Not in the sovereign chain allow-list"
+        );
+    }
+
+    /// To be called with the delegator's total after the delegation was added
+    /// Direct delegations and shared security allocations count together
+    fn require_user_within_max_per_delegator(&self, sov_id: AddressId, user_id: AddressId) {
+        let delegator_total = self.total_sov_by_user(user_id, sov_id).get()
+            + self.total_shared_sov_by_user(user_id, sov_id).get();
+        self.require_within_max_per_delegator(sov_id, &delegator_total);
+    }
+
+    fn require_within_max_per_delegator(&self, sov_id: AddressId, delegator_total: &BigUint) {
+        let max_per_delegator = self.sov_max_per_delegator(sov_id).get();
+        require!(
+            max_per_delegator == 0 || delegator_total <= &max_per_delegator,
+            "Above the sovereign chain maximum per delegator"
+        );
+    }
+
+    #[storage_mapper("sovAllowListEnabled")]
+    fn sov_allow_list_enabled(&self, sov_id: AddressId) -> SingleValueMapper<bool>;
+
+    #[storage_mapper("sovAllowList")]
+    fn sov_allow_list(&self, sov_id: AddressId) -> UnorderedSetMapper<ManagedAddress>;
+
+    #[storage_mapper("sovMaxPerDelegator")]
+    fn sov_max_per_delegator(&self, sov_id: AddressId) -> SingleValueMapper<BigUint>;
+}
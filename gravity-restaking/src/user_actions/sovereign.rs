@@ -1,6 +1,9 @@
-use crate::{unique_payments::UniquePayments, user_actions::validator::INVALID_MAX_AMOUNT_ERR_MSG};
+use crate::{
+    unique_payments::{PaymentsVec, UniquePayments},
+    user_actions::{validator::INVALID_MAX_AMOUNT_ERR_MSG, validator_metadata::MAX_METADATA_LEN},
+};
 
-use super::common_actions::AddDelegationArgs;
+use super::common_actions::{AddDelegationArgs, StakeCheckpoint};
 
 multiversx_sc::imports!();
 multiversx_sc::derive_imports!();
@@ -14,6 +17,14 @@ pub struct SovereignInfo<M: ManagedTypeApi> {
     pub opt_max_restaking_cap: Option<BigUint<M>>,
 }
 
+#[derive(TypeAbi, TopEncode, TopDecode, NestedEncode, NestedDecode, Clone, Copy, PartialEq)]
+pub enum SovState {
+    Pending,
+    Active,
+    Paused,
+    Sunset,
+}
+
 impl<M: ManagedTypeApi> SovereignInfo<M> {
     #[inline]
     pub fn new(name: ManagedBuffer<M>, description: ManagedBuffer<M>) -> Self {
@@ -36,8 +47,19 @@ pub trait SovereignModule:
     #[endpoint(registerSov)]
     fn register_sov(&self, name: ManagedBuffer, description: ManagedBuffer) {
         self.require_not_empty_buffer(&name);
+        require!(name.len() <= MAX_METADATA_LEN, "Name too long");
+        require!(
+            description.len() <= MAX_METADATA_LEN,
+            "Description too long"
+        );
 
         let caller = self.blockchain().get_caller();
+        require!(
+            self.resolve_sov_id(&caller) == NULL_ID,
+            "Already owns a sovereign chain"
+        );
+        // an address that transferred its chain may still be mapped to the old ID
+        let _ = self.sov_id().remove_by_address(&caller);
         let caller_id = self.sov_id().insert_new(&caller);
 
         let id_for_name_mapper = self.sov_chain_for_name(&name);
@@ -45,11 +67,42 @@ pub trait SovereignModule:
 
         let sov_info = SovereignInfo::new(name, description);
         self.sov_info(caller_id).set(&sov_info);
+        self.sov_state(caller_id).set(SovState::Pending);
         id_for_name_mapper.set(caller_id);
 
         self.emit_sov_register_event(caller, sov_info);
     }
 
+    /// Delegations are keyed by ID, so they are kept when the name changes
+    #[endpoint(updateSovInfo)]
+    fn update_sov_info(&self, description: ManagedBuffer, opt_name: OptionalValue<ManagedBuffer>) {
+        require!(
+            description.len() <= MAX_METADATA_LEN,
+            "Description too long"
+        );
+
+        let caller = self.blockchain().get_caller();
+        let sov_id = self.resolve_sov_id_non_zero(&caller);
+
+        let mut sov_info = self.sov_info(sov_id).get();
+        if let OptionalValue::Some(new_name) = opt_name {
+            self.require_not_empty_buffer(&new_name);
+            require!(new_name.len() <= MAX_METADATA_LEN, "Name too long");
+
+            let new_name_mapper = self.sov_chain_for_name(&new_name);
+            require!(new_name_mapper.is_empty(), "Name already taken");
+
+            self.sov_chain_for_name(&sov_info.name).clear();
+            new_name_mapper.set(sov_id);
+            sov_info.name = new_name;
+        }
+
+        sov_info.description = description;
+        self.sov_info(sov_id).set(&sov_info);
+
+        self.emit_sov_update_info_event(caller, sov_info);
+    }
+
     #[endpoint(setUpRewards)]
     fn set_up_rewards(
         &self,
@@ -59,22 +112,10 @@ pub trait SovereignModule:
         /* _computation: ???, */
     ) {
         let caller = self.blockchain().get_caller();
-        let _caller_id = self.sov_id().get_id_non_zero(&caller);
+        let _caller_id = self.resolve_sov_id_non_zero(&caller);
         // TODO: Unsure what to do with all this info yet
     }
 
-    #[endpoint(unRegister)]
-    fn unregister(&self) {
-        let caller = self.blockchain().get_caller();
-        let caller_id = self.sov_id().remove_by_address(&caller);
-        require!(caller_id != NULL_ID, "Unknown sovereign chain");
-
-        let sov_info = self.sov_info(caller_id).take();
-        self.sov_chain_for_name(&sov_info.name).clear();
-
-        self.emit_sov_unregister_event(caller);
-    }
-
     #[payable("*")]
     #[endpoint(addRewards)]
     fn add_rewards(&self) {
@@ -85,29 +126,33 @@ pub trait SovereignModule:
     #[endpoint(addOwnSecurityFunds)]
     fn add_own_security_funds(&self) {
         let sov_chain = self.blockchain().get_caller();
-        let sov_id = self.sov_id().get_id_non_zero(&sov_chain);
+        let sov_id = self.resolve_sov_id_non_zero(&sov_chain);
+        self.require_sov_accepts_delegation(sov_id);
+
         let sov_info = self.sov_info(sov_id).get();
         let user_id_of_sov_chain = self.user_ids().get_id_or_insert(&sov_chain);
 
         let payments = self.get_non_empty_payments();
-        let mut total = BigUint::zero();
-        for payment in &payments {
-            self.require_token_in_whitelist(&payment.token_identifier);
-
-            total += self.get_total_staked_egld(&payment.token_identifier, &payment.amount);
-        }
+        let total = self.get_whitelisted_payments_total(&payments);
+        self.require_valid_sov_delegation(sov_id, &payments, &total);
 
         let args = AddDelegationArgs {
             total_delegated_mapper: self.total_delegated_sov_amount(sov_id),
             total_by_user_mapper: self.total_sov_by_user(user_id_of_sov_chain, sov_id),
+            target_checkpoints_mapper: self.sov_stake_checkpoints(sov_id),
+            user_checkpoints_mapper: self.user_sov_stake_checkpoints(user_id_of_sov_chain, sov_id),
             all_delegators_mapper: &mut self.all_sov_delegators(sov_id),
+            user_targets_mapper: &mut self.sovs_of_user(user_id_of_sov_chain),
             delegated_by_mapper: self.delegated_sov_by(user_id_of_sov_chain, sov_id),
             opt_max_delegation: sov_info.opt_max_restaking_cap,
             payments_to_add: payments.clone(),
             total_amount: total,
+            target_id: sov_id,
             caller_id: user_id_of_sov_chain,
         };
         self.add_delegation(args);
+        self.try_activate_sov(sov_id, &sov_chain);
+        self.add_total_deposited(&payments);
 
         self.sov_add_own_security_funds_event(sov_chain, payments);
     }
@@ -115,9 +160,9 @@ pub trait SovereignModule:
     #[endpoint(setMaxReStakingCap)]
     fn set_max_restaking_cap(&self, max_cap: BigUint) {
         let caller = self.blockchain().get_caller();
-        let sov_id = self.sov_id().get_id_non_zero(&caller);
+        let sov_id = self.resolve_sov_id_non_zero(&caller);
         self.sov_info(sov_id).update(|sov_info| {
-            let current_total = self.total_delegated_sov_amount(sov_id).get();
+            let current_total = self.get_total_sov_security(sov_id);
             require!(max_cap >= current_total, INVALID_MAX_AMOUNT_ERR_MSG);
 
             sov_info.opt_max_restaking_cap = Some(max_cap.clone());
@@ -126,29 +171,274 @@ pub trait SovereignModule:
         self.emit_sov_set_max_restaking_cap_event(caller, max_cap);
     }
 
+    /// Minimum value of a single delegation
+    #[endpoint(setMinDelegation)]
+    fn set_min_delegation(&self, min_delegation: BigUint) {
+        let caller = self.blockchain().get_caller();
+        let sov_id = self.resolve_sov_id_non_zero(&caller);
+        self.sov_min_delegation(sov_id).set(&min_delegation);
+
+        self.emit_sov_set_min_delegation_event(caller, min_delegation);
+    }
+
+    /// Total delegated value required before the chain can go live
+    #[endpoint(setMinTotalSecurity)]
+    fn set_min_total_security(&self, min_total_security: BigUint) {
+        let caller = self.blockchain().get_caller();
+        let sov_id = self.resolve_sov_id_non_zero(&caller);
+        self.sov_min_total_security(sov_id).set(&min_total_security);
+        self.try_activate_sov(sov_id, &caller);
+
+        self.emit_sov_set_min_total_security_event(caller, min_total_security);
+    }
+
+    /// New delegations are refused while paused
+    #[endpoint(pause)]
+    fn pause(&self) {
+        let caller = self.blockchain().get_caller();
+        let sov_id = self.resolve_sov_id_non_zero(&caller);
+        require!(
+            self.sov_state(sov_id).get() == SovState::Active,
+            "Sovereign chain is not active"
+        );
+
+        self.change_sov_state(sov_id, &caller, SovState::Paused);
+    }
+
+    #[endpoint(resume)]
+    fn resume(&self) {
+        let caller = self.blockchain().get_caller();
+        let sov_id = self.resolve_sov_id_non_zero(&caller);
+        require!(
+            self.sov_state(sov_id).get() == SovState::Paused,
+            "Sovereign chain is not paused"
+        );
+
+        self.change_sov_state(sov_id, &caller, SovState::Pending);
+        self.try_activate_sov(sov_id, &caller);
+    }
+
+    /// Maximum number of validators allocating to the chain. 0 means no limit.
+    #[endpoint(setTargetValidatorCount)]
+    fn set_target_validator_count(&self, target_validator_count: usize) {
+        let caller = self.blockchain().get_caller();
+        let sov_id = self.resolve_sov_id_non_zero(&caller);
+        self.sov_target_validator_count(sov_id)
+            .set(target_validator_count);
+
+        self.emit_sov_set_target_validator_count_event(caller, target_validator_count);
+    }
+
     #[view(getSovInfo)]
-    fn get_sov_info(&self, sov_address: ManagedAddress) -> SovereignInfo<Self::Api> {
-        let sov_id = self.sov_id().get_id_non_zero(&sov_address);
+    fn get_sov_info(&self, sov_address: ManagedAddress) -> OptionalValue<SovereignInfo<Self::Api>> {
+        let sov_id = self.resolve_sov_id(&sov_address);
+        if sov_id == NULL_ID {
+            return OptionalValue::None;
+        }
+
+        OptionalValue::Some(self.sov_info(sov_id).get())
+    }
+
+    #[view(getTotalDelegatedSovAmount)]
+    fn get_total_delegated_sov_amount(&self, sov_address: ManagedAddress) -> BigUint {
+        let sov_id = self.resolve_sov_id(&sov_address);
+        if sov_id == NULL_ID {
+            return BigUint::zero();
+        }
+
+        self.total_delegated_sov_amount(sov_id).get()
+    }
+
+    #[view(getSovAcceptedTokens)]
+    fn get_sov_accepted_tokens(
+        &self,
+        sov_address: ManagedAddress,
+    ) -> MultiValueEncoded<TokenIdentifier> {
+        let mut result = MultiValueEncoded::new();
+        let sov_id = self.resolve_sov_id(&sov_address);
+        if sov_id == NULL_ID {
+            return result;
+        }
+
+        for token_id in self.sov_accepted_tokens(sov_id).iter() {
+            result.push(token_id);
+        }
+
+        result
+    }
+
+    #[view(getSovState)]
+    fn get_sov_state(&self, sov_address: ManagedAddress) -> OptionalValue<SovState> {
+        let sov_id = self.resolve_sov_id(&sov_address);
+        if sov_id == NULL_ID {
+            return OptionalValue::None;
+        }
+
+        OptionalValue::Some(self.sov_state(sov_id).get())
+    }
+
+    #[view(hasMinTotalSecurity)]
+    fn has_min_total_security(&self, sov_address: ManagedAddress) -> bool {
+        let sov_id = self.resolve_sov_id(&sov_address);
+        if sov_id == NULL_ID {
+            return false;
+        }
+
+        self.has_min_total_security_by_id(sov_id)
+    }
 
-        self.sov_info(sov_id).get()
+    fn has_min_total_security_by_id(&self, sov_id: AddressId) -> bool {
+        self.get_total_sov_security(sov_id) >= self.sov_min_total_security(sov_id).get()
+    }
+
+    /// Delegated stake plus the stake validators allocated to the chain
+    fn get_total_sov_security(&self, sov_id: AddressId) -> BigUint {
+        self.total_delegated_sov_amount(sov_id).get()
+            + self.total_operator_allocated_sov(sov_id).get()
+    }
+
+    fn require_valid_sov_delegation(
+        &self,
+        sov_id: AddressId,
+        payments: &PaymentsVec<Self::Api>,
+        total: &BigUint,
+    ) {
+        let accepted_tokens_mapper = self.sov_accepted_tokens(sov_id);
+        if !accepted_tokens_mapper.is_empty() {
+            for payment in payments {
+                require!(
+                    accepted_tokens_mapper.contains(&payment.token_identifier),
+                    "Token not accepted by the sovereign chain"
+                );
+            }
+        }
+
+        require!(
+            total >= &self.sov_min_delegation(sov_id).get(),
+            "Delegation below the sovereign chain minimum"
+        );
+    }
+
+    /// IDs stay the same across ownership transfers, the current owner being tracked separately.
+    /// Chains never transferred are owned by their registration address.
+    fn resolve_sov_id(&self, address: &ManagedAddress) -> AddressId {
+        let owned_id = self.sov_id_of_owner(address).get();
+        if owned_id != NULL_ID {
+            return owned_id;
+        }
+
+        let sov_id = self.sov_id().get_id(address);
+        if sov_id == NULL_ID || !self.sov_owner(sov_id).is_empty() {
+            return NULL_ID;
+        }
+
+        sov_id
+    }
+
+    fn resolve_sov_id_non_zero(&self, address: &ManagedAddress) -> AddressId {
+        let sov_id = self.resolve_sov_id(address);
+        require!(sov_id != NULL_ID, "Unknown sovereign chain");
+
+        sov_id
+    }
+
+    fn get_sov_owner(&self, sov_id: AddressId) -> Option<ManagedAddress> {
+        let owner_mapper = self.sov_owner(sov_id);
+        if !owner_mapper.is_empty() {
+            return Some(owner_mapper.get());
+        }
+
+        self.sov_id().get_address(sov_id)
+    }
+
+    fn set_sov_owner(
+        &self,
+        sov_id: AddressId,
+        old_owner: &ManagedAddress,
+        new_owner: &ManagedAddress,
+    ) {
+        self.sov_id_of_owner(old_owner).clear();
+        self.sov_id_of_owner(new_owner).set(sov_id);
+        self.sov_owner(sov_id).set(new_owner);
+
+        // the old owner may register a new chain
+        let _ = self.sov_id().remove_by_address(old_owner);
+    }
+
+    fn is_registered_sov(&self, sov_id: AddressId) -> bool {
+        self.get_sov_owner(sov_id).is_some()
     }
 
     fn require_valid_sov_id(&self, sov_id: AddressId) {
         require!(sov_id != NULL_ID, "Invalid chain name");
     }
 
+    fn require_sov_accepts_delegation(&self, sov_id: AddressId) {
+        let sov_state = self.sov_state(sov_id).get();
+        require!(
+            sov_state == SovState::Pending || sov_state == SovState::Active,
+            "Sovereign chain does not accept delegations"
+        );
+    }
+
+    /// A pending chain goes live once it has the minimum total security
+    fn try_activate_sov(&self, sov_id: AddressId, sov_address: &ManagedAddress) {
+        if self.sov_state(sov_id).get() == SovState::Pending
+            && self.has_min_total_security_by_id(sov_id)
+        {
+            self.change_sov_state(sov_id, sov_address, SovState::Active);
+        }
+    }
+
+    fn change_sov_state(
+        &self,
+        sov_id: AddressId,
+        sov_address: &ManagedAddress,
+        new_state: SovState,
+    ) {
+        let state_mapper = self.sov_state(sov_id);
+        let old_state = state_mapper.get();
+        state_mapper.set(new_state);
+
+        self.emit_sov_state_change_event(sov_address.clone(), old_state, new_state);
+    }
+
     #[storage_mapper("sovId")]
     fn sov_id(&self) -> AddressToIdMapper<Self::Api>;
 
+    #[storage_mapper("sovOwner")]
+    fn sov_owner(&self, sov_id: AddressId) -> SingleValueMapper<ManagedAddress>;
+
+    #[storage_mapper("sovIdOfOwner")]
+    fn sov_id_of_owner(&self, owner: &ManagedAddress) -> SingleValueMapper<AddressId>;
+
     #[storage_mapper("sovInfo")]
     fn sov_info(&self, sov_id: AddressId) -> SingleValueMapper<SovereignInfo<Self::Api>>;
 
     #[storage_mapper("sovForName")]
     fn sov_chain_for_name(&self, name: &ManagedBuffer) -> SingleValueMapper<AddressId>;
 
+    #[storage_mapper("sovAcceptedTokens")]
+    fn sov_accepted_tokens(&self, sov_id: AddressId) -> UnorderedSetMapper<TokenIdentifier>;
+
+    #[storage_mapper("sovMinDelegation")]
+    fn sov_min_delegation(&self, sov_id: AddressId) -> SingleValueMapper<BigUint>;
+
+    #[storage_mapper("sovMinTotalSecurity")]
+    fn sov_min_total_security(&self, sov_id: AddressId) -> SingleValueMapper<BigUint>;
+
+    #[storage_mapper("sovTargetValidatorCount")]
+    fn sov_target_validator_count(&self, sov_id: AddressId) -> SingleValueMapper<usize>;
+
+    #[storage_mapper("sovState")]
+    fn sov_state(&self, sov_id: AddressId) -> SingleValueMapper<SovState>;
+
     #[storage_mapper("allSovDelegators")]
     fn all_sov_delegators(&self, sov_id: AddressId) -> UnorderedSetMapper<AddressId>;
 
+    #[storage_mapper("sovsOfUser")]
+    fn sovs_of_user(&self, user_id: AddressId) -> UnorderedSetMapper<AddressId>;
+
     #[storage_mapper("delegatedSovBy")]
     fn delegated_sov_by(
         &self,
@@ -159,10 +449,31 @@ pub trait SovereignModule:
     #[storage_mapper("totalDelegatedSovAmount")]
     fn total_delegated_sov_amount(&self, sov_id: AddressId) -> SingleValueMapper<BigUint>;
 
+    #[storage_mapper("totalOperatorAllocatedSov")]
+    fn total_operator_allocated_sov(&self, sov_id: AddressId) -> SingleValueMapper<BigUint>;
+
     #[storage_mapper("totalSovByUser")]
     fn total_sov_by_user(
         &self,
         user_id: AddressId,
         sov_id: AddressId,
     ) -> SingleValueMapper<BigUint>;
+
+    #[storage_mapper("totalSharedSovByUser")]
+    fn total_shared_sov_by_user(
+        &self,
+        user_id: AddressId,
+        sov_id: AddressId,
+    ) -> SingleValueMapper<BigUint>;
+
+    /// Includes shared security allocations
+    #[storage_mapper("sovStakeCheckpoints")]
+    fn sov_stake_checkpoints(&self, sov_id: AddressId) -> VecMapper<StakeCheckpoint<Self::Api>>;
+
+    #[storage_mapper("userSovStakeCheckpoints")]
+    fn user_sov_stake_checkpoints(
+        &self,
+        user_id: AddressId,
+        sov_id: AddressId,
+    ) -> VecMapper<StakeCheckpoint<Self::Api>>;
 }
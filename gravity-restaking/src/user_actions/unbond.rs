@@ -45,9 +45,17 @@ pub trait UnbondModule: super::common_storage::CommonStorageModule {
 
     #[view(getUserUnbondInfo)]
     fn get_user_unbond_info(&self, user: ManagedAddress) -> ManagedVec<UnbondInfo<Self::Api>> {
-        let user_id = self.user_ids().get_id_non_zero(&user);
+        let user_id = self.user_ids().get_id(&user);
+        if user_id == NULL_ID {
+            return ManagedVec::new();
+        }
+
+        let unbond_mapper = self.unbond_info(user_id);
+        if unbond_mapper.is_empty() {
+            return ManagedVec::new();
+        }
 
-        self.unbond_info(user_id).get()
+        unbond_mapper.get()
     }
 
     fn add_unbond_tokens(&self, user_id: AddressId, tokens: UniquePayments<Self::Api>) {
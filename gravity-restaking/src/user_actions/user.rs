@@ -14,10 +14,13 @@ pub type PaymentsMultiValue<M> =
 pub trait UserModule:
     crate::token_whitelist::TokenWhitelistModule
     + super::validator::ValidatorModule
+    + super::self_bond::SelfBondModule
     + super::sovereign::SovereignModule
+    + super::sov_allow_list::SovAllowListModule
     + super::unbond::UnbondModule
     + super::common_actions::CommonActionsModule
     + super::common_storage::CommonStorageModule
+    + super::delegation_rewards::DelegationRewardsModule
     + crate::events::user_events::UserEventsModule
     + crate::events::validator_events::ValidatorEventsModule
     + crate::events::sov_events::SovEventsModule
@@ -29,6 +32,7 @@ pub trait UserModule:
         let payments = self.get_non_empty_payments();
         let caller = self.blockchain().get_caller();
         self.deposit_common(&caller, &payments);
+        self.add_total_deposited(&payments);
 
         self.emit_user_deposit_event(caller, payments);
     }
@@ -61,6 +65,13 @@ pub trait UserModule:
             }
         });
 
+        let mut withdrawn_payments = output_payments.clone();
+        if total_egld > 0 {
+            withdrawn_payments.push(EsdtTokenPayment::new(egld_token_id, 0, total_egld.clone()));
+        }
+        self.deduct_total_deposited(&withdrawn_payments);
+        self.remove_withdrawn_moved_stake(caller_id, &total_egld);
+
         self.send().direct_non_zero_egld(&caller, &total_egld);
 
         if !output_payments.is_empty() {
@@ -81,6 +92,7 @@ pub trait UserModule:
 
         let mut output_payments = user_tokens.into_payments();
         require!(!output_payments.is_empty(), "Nothing to withdraw");
+        self.deduct_total_deposited(&output_payments);
 
         let egld_token_id = TokenIdentifier::from_esdt_bytes(EGLD_TOKEN_ID);
         let mut opt_index_to_remove = None;
@@ -95,6 +107,7 @@ pub trait UserModule:
         if let Some(index_to_remove) = opt_index_to_remove {
             let egld_payment = output_payments.get(index_to_remove);
             output_payments.remove(index_to_remove);
+            self.remove_withdrawn_moved_stake(caller_id, &egld_payment.amount);
 
             self.send().direct_egld(&caller, &egld_payment.amount);
         }
@@ -114,7 +127,9 @@ pub trait UserModule:
 
         let caller = self.blockchain().get_caller();
         let caller_id = self.user_ids().get_id_non_zero(&caller);
-        let validator_id = self.validator_id().get_id_non_zero(&validator);
+        let validator_id = self.resolve_validator_id_non_zero(&validator);
+        self.require_validator_not_unregistering(validator_id);
+
         let validator_config = self.validator_config(validator_id).get();
         let (output_payments, total) =
             self.before_add_delegation(self.user_tokens(caller_id), tokens);
@@ -122,14 +137,20 @@ pub trait UserModule:
         let args = AddDelegationArgs {
             total_delegated_mapper: self.total_delegated_amount(validator_id),
             total_by_user_mapper: self.total_by_user(caller_id, validator_id),
+            target_checkpoints_mapper: self.validator_stake_checkpoints(validator_id),
+            user_checkpoints_mapper: self.user_validator_stake_checkpoints(caller_id, validator_id),
             all_delegators_mapper: &mut self.all_delegators(validator_id),
+            user_targets_mapper: &mut self.validators_of_user(caller_id),
             delegated_by_mapper: self.delegated_by(caller_id, validator_id),
             opt_max_delegation: validator_config.opt_max_delegation,
             payments_to_add: output_payments.clone(),
             total_amount: total,
+            target_id: validator_id,
             caller_id,
         };
         self.add_delegation(args);
+        self.sync_validator_allocation_capacity(caller_id, validator_id);
+        self.require_accepts_delegation(validator_id, &validator, &caller);
 
         self.emit_delegate_validator_event(caller, validator, output_payments);
     }
@@ -146,27 +167,114 @@ pub trait UserModule:
         let caller_id = self.user_ids().get_id_non_zero(&caller);
         let sov_id = self.sov_chain_for_name(&sov_name).get();
         self.require_valid_sov_id(sov_id);
+        self.require_sov_accepts_delegation(sov_id);
 
         let sov_info = self.sov_info(sov_id).get();
         let (output_payments, total) =
             self.before_add_delegation(self.user_tokens(caller_id), tokens);
+        self.require_valid_sov_delegation(sov_id, &output_payments, &total);
 
         let args = AddDelegationArgs {
             total_delegated_mapper: self.total_delegated_sov_amount(sov_id),
             total_by_user_mapper: self.total_sov_by_user(caller_id, sov_id),
+            target_checkpoints_mapper: self.sov_stake_checkpoints(sov_id),
+            user_checkpoints_mapper: self.user_sov_stake_checkpoints(caller_id, sov_id),
             all_delegators_mapper: &mut self.all_sov_delegators(sov_id),
+            user_targets_mapper: &mut self.sovs_of_user(caller_id),
             delegated_by_mapper: self.delegated_sov_by(caller_id, sov_id),
             opt_max_delegation: sov_info.opt_max_restaking_cap,
             payments_to_add: output_payments.clone(),
             total_amount: total,
+            target_id: sov_id,
             caller_id,
         };
         self.add_delegation(args);
 
-        let sov_address = unsafe { self.sov_id().get_address(sov_id).unwrap_unchecked() };
+        self.require_allowed_for_sov(sov_id, &caller);
+        self.require_user_within_max_per_delegator(sov_id, caller_id);
+
+        let sov_address = unsafe { self.get_sov_owner(sov_id).unwrap_unchecked() };
+        self.try_activate_sov(sov_id, &sov_address);
+
         self.emit_delgate_sov_event(caller, sov_address, output_payments);
     }
 
+    #[payable("*")]
+    #[endpoint(depositAndDelegateToValidator)]
+    fn deposit_and_delegate_to_validator(&self, validator: ManagedAddress) {
+        let payments = self.get_non_empty_payments();
+        let caller = self.blockchain().get_caller();
+        let caller_id = self.user_ids().get_id_or_insert(&caller);
+        let validator_id = self.resolve_validator_id_non_zero(&validator);
+        self.require_validator_not_unregistering(validator_id);
+
+        let validator_config = self.validator_config(validator_id).get();
+        let total = self.get_whitelisted_payments_total(&payments);
+
+        let args = AddDelegationArgs {
+            total_delegated_mapper: self.total_delegated_amount(validator_id),
+            total_by_user_mapper: self.total_by_user(caller_id, validator_id),
+            target_checkpoints_mapper: self.validator_stake_checkpoints(validator_id),
+            user_checkpoints_mapper: self.user_validator_stake_checkpoints(caller_id, validator_id),
+            all_delegators_mapper: &mut self.all_delegators(validator_id),
+            user_targets_mapper: &mut self.validators_of_user(caller_id),
+            delegated_by_mapper: self.delegated_by(caller_id, validator_id),
+            opt_max_delegation: validator_config.opt_max_delegation,
+            payments_to_add: payments.clone(),
+            total_amount: total,
+            target_id: validator_id,
+            caller_id,
+        };
+        self.add_delegation(args);
+        self.sync_validator_allocation_capacity(caller_id, validator_id);
+        self.require_accepts_delegation(validator_id, &validator, &caller);
+        self.add_total_deposited(&payments);
+
+        self.emit_user_deposit_event(caller.clone(), payments.clone());
+        self.emit_delegate_validator_event(caller, validator, payments);
+    }
+
+    #[payable("*")]
+    #[endpoint(depositAndDelegateForSovereignChain)]
+    fn deposit_and_delegate_for_sovereign_chain(&self, sov_name: ManagedBuffer) {
+        let payments = self.get_non_empty_payments();
+        let caller = self.blockchain().get_caller();
+        let caller_id = self.user_ids().get_id_or_insert(&caller);
+        let sov_id = self.sov_chain_for_name(&sov_name).get();
+        self.require_valid_sov_id(sov_id);
+        self.require_sov_accepts_delegation(sov_id);
+
+        let sov_info = self.sov_info(sov_id).get();
+        let total = self.get_whitelisted_payments_total(&payments);
+        self.require_valid_sov_delegation(sov_id, &payments, &total);
+
+        let args = AddDelegationArgs {
+            total_delegated_mapper: self.total_delegated_sov_amount(sov_id),
+            total_by_user_mapper: self.total_sov_by_user(caller_id, sov_id),
+            target_checkpoints_mapper: self.sov_stake_checkpoints(sov_id),
+            user_checkpoints_mapper: self.user_sov_stake_checkpoints(caller_id, sov_id),
+            all_delegators_mapper: &mut self.all_sov_delegators(sov_id),
+            user_targets_mapper: &mut self.sovs_of_user(caller_id),
+            delegated_by_mapper: self.delegated_sov_by(caller_id, sov_id),
+            opt_max_delegation: sov_info.opt_max_restaking_cap,
+            payments_to_add: payments.clone(),
+            total_amount: total,
+            target_id: sov_id,
+            caller_id,
+        };
+        self.add_delegation(args);
+
+        self.require_allowed_for_sov(sov_id, &caller);
+        self.require_user_within_max_per_delegator(sov_id, caller_id);
+
+        let sov_address = unsafe { self.get_sov_owner(sov_id).unwrap_unchecked() };
+        self.try_activate_sov(sov_id, &sov_address);
+        self.add_total_deposited(&payments);
+
+        self.emit_user_deposit_event(caller.clone(), payments.clone());
+        self.emit_delgate_sov_event(caller, sov_address, payments);
+    }
+
     #[endpoint(revokeDelegationFromValidator)]
     fn revoke_delegation_from_validator(
         &self,
@@ -177,17 +285,23 @@ pub trait UserModule:
 
         let caller = self.blockchain().get_caller();
         let caller_id = self.user_ids().get_id_non_zero(&caller);
-        let validator_id = self.validator_id().get_id_non_zero(&validator);
+        let validator_id = self.resolve_validator_id_non_zero(&validator);
 
         let args = RemoveDelegationArgs {
             total_delegated_mapper: self.total_delegated_amount(validator_id),
             total_by_user_mapper: self.total_by_user(caller_id, validator_id),
+            target_checkpoints_mapper: self.validator_stake_checkpoints(validator_id),
+            user_checkpoints_mapper: self.user_validator_stake_checkpoints(caller_id, validator_id),
             all_delegators_mapper: &mut self.all_delegators(validator_id),
+            user_targets_mapper: &mut self.validators_of_user(caller_id),
             delegated_by_mapper: self.delegated_by(caller_id, validator_id),
             tokens,
+            target_id: validator_id,
             caller_id,
         };
         let output_unique_payments = self.remove_delegation(args);
+        self.sync_validator_allocation_capacity(caller_id, validator_id);
+        self.require_self_bond_after_revoke(validator_id, &validator, &caller);
         self.add_unbond_tokens(caller_id, output_unique_payments.clone());
 
         self.emit_revoke_validator_event(caller, validator, output_unique_payments);
@@ -209,27 +323,70 @@ pub trait UserModule:
         let args = RemoveDelegationArgs {
             total_delegated_mapper: self.total_delegated_sov_amount(sov_id),
             total_by_user_mapper: self.total_sov_by_user(caller_id, sov_id),
+            target_checkpoints_mapper: self.sov_stake_checkpoints(sov_id),
+            user_checkpoints_mapper: self.user_sov_stake_checkpoints(caller_id, sov_id),
             all_delegators_mapper: &mut self.all_sov_delegators(sov_id),
+            user_targets_mapper: &mut self.sovs_of_user(caller_id),
             delegated_by_mapper: self.delegated_sov_by(caller_id, sov_id),
             tokens,
+            target_id: sov_id,
             caller_id,
         };
         let output_unique_payments = self.remove_delegation(args);
         self.add_unbond_tokens(caller_id, output_unique_payments.clone());
 
-        let sov_address = unsafe { self.sov_id().get_address(sov_id).unwrap_unchecked() };
+        let sov_address = unsafe { self.get_sov_owner(sov_id).unwrap_unchecked() };
         self.emit_revoke_sov_event(caller, sov_address, output_unique_payments);
     }
 
+    /// One-time seed of the deposited total with the funds held before it was tracked
+    #[only_owner]
+    #[endpoint(seedTotalDeposited)]
+    fn seed_total_deposited(&self, tokens: PaymentsMultiValue<Self::Api>) {
+        self.require_non_empty_args(&tokens);
+        require!(
+            !self.total_deposited_seeded().get(),
+            "Total deposited already seeded"
+        );
+        self.total_deposited_seeded().set(true);
+
+        let mut payments = PaymentsVec::new();
+        for token_tuple in tokens {
+            let (token_id, nonce, amount) = token_tuple.into_tuple();
+            payments.push(EsdtTokenPayment::new(token_id, nonce, amount));
+        }
+        self.add_total_deposited(&payments);
+    }
+
     #[endpoint(unbondTokensCaller)]
     fn unbond_tokens_caller(&self) {
         let caller = self.blockchain().get_caller();
         let output_payments = self.unbond_common(&caller);
-        if !output_payments.is_empty() {
-            self.send().direct_multi(&caller, &output_payments);
+        if output_payments.is_empty() {
+            return;
+        }
 
-            self.emit_unbond_tokens_caller_event(caller, output_payments);
+        self.deduct_total_deposited(&output_payments);
+
+        let egld_token_id = TokenIdentifier::from_esdt_bytes(EGLD_TOKEN_ID);
+        let mut esdt_payments = PaymentsVec::new();
+        for payment in &output_payments {
+            if payment.token_identifier != egld_token_id {
+                esdt_payments.push(payment);
+
+                continue;
+            }
+
+            let caller_id = self.user_ids().get_id_non_zero(&caller);
+            self.remove_withdrawn_moved_stake(caller_id, &payment.amount);
+            self.send().direct_egld(&caller, &payment.amount);
         }
+
+        if !esdt_payments.is_empty() {
+            self.send().direct_multi(&caller, &esdt_payments);
+        }
+
+        self.emit_unbond_tokens_caller_event(caller, output_payments);
     }
 
     #[endpoint(unbondTokensGravityRestaking)]
@@ -273,4 +430,7 @@ pub trait UserModule:
     fn require_non_empty_args(&self, args: &PaymentsMultiValue<Self::Api>) {
         require!(!args.is_empty(), "No arguments");
     }
+
+    #[storage_mapper("totalDepositedSeeded")]
+    fn total_deposited_seeded(&self) -> SingleValueMapper<bool>;
 }
@@ -0,0 +1,54 @@
+multiversx_sc::imports!();
+
+#[multiversx_sc::module]
+pub trait UserTargetsModule:
+    super::shared_security::SharedSecurityModule
+    + super::user::UserModule
+    + crate::token_whitelist::TokenWhitelistModule
+    + super::validator::ValidatorModule
+    + super::sovereign::SovereignModule
+    + super::common_actions::CommonActionsModule
+    + super::common_storage::CommonStorageModule
+    + utils::UtilsModule
+{
+    /// Indexes the user's delegations made before targets were tracked per user,
+    /// so they show up in the portfolio.
+    /// Only the validator and sovereign IDs between first_id and last_id are checked,
+    /// so the backfill can be split into several calls.
+    #[only_owner]
+    #[endpoint(backfillUserTargets)]
+    fn backfill_user_targets(&self, user: ManagedAddress, first_id: AddressId, last_id: AddressId) {
+        require!(
+            first_id > NULL_ID && first_id <= last_id,
+            "Invalid ID range"
+        );
+
+        let user_id = self.user_ids().get_id_non_zero(&user);
+
+        let last_validator_id = core::cmp::min(last_id, self.validator_id().get_last_id());
+        let mut validators_of_user_mapper = self.validators_of_user(user_id);
+        for validator_id in first_id..=last_validator_id {
+            if self.is_registered_validator(validator_id)
+                && !self.delegated_by(user_id, validator_id).is_empty()
+            {
+                let _ = validators_of_user_mapper.insert(validator_id);
+            }
+        }
+
+        let last_sov_id = core::cmp::min(last_id, self.sov_id().get_last_id());
+        let mut sovs_of_user_mapper = self.sovs_of_user(user_id);
+        let mut shared_sovs_of_user_mapper = self.shared_sovs_of_user(user_id);
+        for sov_id in first_id..=last_sov_id {
+            if !self.is_registered_sov(sov_id) {
+                continue;
+            }
+
+            if !self.delegated_sov_by(user_id, sov_id).is_empty() {
+                let _ = sovs_of_user_mapper.insert(sov_id);
+            }
+            if !self.shared_sov_allocation(user_id, sov_id).is_empty() {
+                let _ = shared_sovs_of_user_mapper.insert(sov_id);
+            }
+        }
+    }
+}
@@ -1,13 +1,17 @@
 use crate::unique_payments::UniquePayments;
 
-use super::common_actions::AddDelegationArgs;
+use super::{
+    common_actions::{AddDelegationArgs, StakeCheckpoint},
+    sovereign::Epoch,
+    validator_metadata::MAX_METADATA_LEN,
+};
 
 multiversx_sc::imports!();
 multiversx_sc::derive_imports!();
 
 const BLS_KEY_LEN: usize = 96;
 const BLS_SIG_LEN: usize = 48;
-const MAX_PERCENT: Percent = 10_000;
+pub const MAX_PERCENT: Percent = 10_000;
 
 pub static INVALID_MAX_AMOUNT_ERR_MSG: &[u8] = b"Cannot set max below the current delegated amount";
 
@@ -18,6 +22,7 @@ pub type Percent = u32;
 #[derive(TypeAbi, TopEncode, TopDecode, NestedEncode, NestedDecode)]
 pub struct ValidatorConfig<M: ManagedTypeApi> {
     pub name: ManagedBuffer<M>,
+    /// Keys added before they were tracked in blsKeys, moved there by migrateBlsKeys
     pub bls_keys: ManagedVec<M, BlsKey<M>>,
     pub fee: Percent,
     pub opt_max_delegation: Option<BigUint<M>>,
@@ -34,19 +39,44 @@ impl<M: ManagedTypeApi> ValidatorConfig<M> {
     }
 }
 
+#[derive(TypeAbi, TopEncode, TopDecode, NestedEncode, NestedDecode, Clone, Copy, PartialEq)]
+pub enum BlsKeyStatus {
+    Pending,
+    Active,
+    Jailed,
+    Unstaked,
+}
+
+#[derive(TypeAbi, TopEncode, TopDecode, NestedEncode, NestedDecode)]
+pub struct PendingFee {
+    pub fee: Percent,
+    pub announce_epoch: Epoch,
+    pub effective_epoch: Epoch,
+}
+
 #[multiversx_sc::module]
 pub trait ValidatorModule:
     crate::token_whitelist::TokenWhitelistModule
+    + crate::user_actions::allocation_capacity::AllocationCapacityModule
+    + crate::user_actions::sovereign::SovereignModule
     + crate::user_actions::common_actions::CommonActionsModule
     + crate::user_actions::common_storage::CommonStorageModule
     + crate::events::validator_events::ValidatorEventsModule
+    + crate::events::sov_events::SovEventsModule
     + utils::UtilsModule
 {
     #[endpoint]
     fn register(&self, name: ManagedBuffer) {
         self.require_not_empty_buffer(&name);
+        require!(name.len() <= MAX_METADATA_LEN, "Name too long");
 
         let caller = self.blockchain().get_caller();
+        require!(
+            self.resolve_validator_id(&caller) == NULL_ID,
+            "Already owns a validator"
+        );
+        // an address that transferred its validator may still be mapped to the old ID
+        let _ = self.validator_id().remove_by_address(&caller);
         let caller_id = self.validator_id().insert_new(&caller);
 
         let id_for_name_mapper = self.id_for_name(&name);
@@ -66,8 +96,10 @@ pub trait ValidatorModule:
         pairs: MultiValueEncoded<MultiValue2<BlsKey<Self::Api>, BlsSignature<Self::Api>>>,
     ) {
         let caller = self.blockchain().get_caller();
-        let caller_id = self.validator_id().get_id_non_zero(&caller);
+        let caller_id = self.resolve_validator_id_non_zero(&caller);
+        self.move_legacy_bls_keys(caller_id);
 
+        let mut bls_keys_mapper = self.bls_keys(caller_id);
         let mut new_bls_keys = ManagedVec::<Self::Api, _>::new();
         for pair in pairs {
             let (bls_key, bls_sig) = pair.into_tuple();
@@ -78,65 +110,132 @@ pub trait ValidatorModule:
             );
             require!(valid_sig, "Invalid BLS signature");
 
+            let key_owner_mapper = self.bls_key_owner(&bls_key);
+            require!(key_owner_mapper.is_empty(), "Key already known");
+
+            key_owner_mapper.set(caller_id);
+            self.bls_key_status(&bls_key).set(BlsKeyStatus::Pending);
+            let _ = bls_keys_mapper.insert(bls_key.clone());
             new_bls_keys.push(bls_key);
         }
 
-        let keys_added = new_bls_keys.clone();
-        self.validator_config(caller_id).update(|config| {
-            while !new_bls_keys.is_empty() {
-                let current_key = new_bls_keys.get(0);
-                for existing_key in &config.bls_keys {
-                    require!(existing_key != *current_key, "Key already known");
-                }
-
-                config.bls_keys.push((*current_key).clone());
-                new_bls_keys.remove(0);
-            }
-        });
-
-        self.emit_validator_add_bls_keys_event(caller, keys_added);
+        self.emit_validator_add_bls_keys_event(caller, new_bls_keys);
     }
 
     #[endpoint(removeKeys)]
     fn remove_keys(&self, keys: MultiValueEncoded<BlsKey<Self::Api>>) {
         let caller = self.blockchain().get_caller();
-        let caller_id = self.validator_id().get_id_non_zero(&caller);
+        let caller_id = self.resolve_validator_id_non_zero(&caller);
+        self.move_legacy_bls_keys(caller_id);
 
-        let mapper = self.validator_config(caller_id);
-        let mut config = mapper.get();
+        let mut bls_keys_mapper = self.bls_keys(caller_id);
         let mut removed_keys = ManagedVec::new();
         for key in keys {
-            let opt_index = config.bls_keys.find(&key);
-            require!(opt_index.is_some(), "Key not found");
+            let key_owner_mapper = self.bls_key_owner(&key);
+            require!(
+                !key_owner_mapper.is_empty() && key_owner_mapper.get() == caller_id,
+                "Key not found"
+            );
 
-            let index = unsafe { opt_index.unwrap_unchecked() };
-            config.bls_keys.remove(index);
+            require!(!self.is_key_jailed(&key), "Cannot remove jailed key");
+
+            key_owner_mapper.clear();
+            self.bls_key_status(&key).clear();
+            let _ = bls_keys_mapper.swap_remove(&key);
             removed_keys.push(key);
         }
 
-        mapper.set(config);
-
         self.emit_validator_remove_bls_keys_event(caller, removed_keys);
     }
 
+    /// Moves the keys kept in the config of the given validators to the key registry
+    #[endpoint(migrateBlsKeys)]
+    fn migrate_bls_keys(&self, validators: MultiValueEncoded<ManagedAddress>) {
+        for validator in validators {
+            let validator_id = self.resolve_validator_id_non_zero(&validator);
+            self.move_legacy_bls_keys(validator_id);
+        }
+    }
+
     // TODO: validateFor@projectID@LIST<BLSKEYS>@LISTOFStakeEGLDAssets
 
+    #[only_owner]
+    #[endpoint(setFeeChangeDelayEpochs)]
+    fn set_fee_change_delay_epochs(&self, delay_epochs: Epoch) {
+        self.fee_change_delay_epochs().set(delay_epochs);
+    }
+
+    /// MAX_PERCENT disables the limit
+    #[only_owner]
+    #[endpoint(setMaxFeeIncrease)]
+    fn set_max_fee_increase(&self, max_increase: Percent) {
+        require!(max_increase <= MAX_PERCENT, "Invalid fee percent");
+
+        self.max_fee_increase().set(max_increase);
+    }
+
+    /// Fee decreases apply instantly.
+    /// Fee increases are announced and only apply after the fee change delay,
+    /// at most one per fee change delay.
+    /// Until then, the stake delegated before the announcement may be redelegated without the cooldown.
     #[endpoint(setUpFee)]
     fn set_up_fee(&self, fee: Percent) {
         require!(fee <= MAX_PERCENT, "Invalid fee percent");
 
         let caller = self.blockchain().get_caller();
-        let caller_id = self.validator_id().get_id_non_zero(&caller);
-        self.validator_config(caller_id)
-            .update(|config| config.fee = fee);
+        let caller_id = self.resolve_validator_id_non_zero(&caller);
+        self.apply_pending_fee(caller_id);
+
+        let current_fee = self.validator_config(caller_id).get().fee;
+        if fee <= current_fee {
+            self.pending_fee(caller_id).clear();
+            self.validator_config(caller_id)
+                .update(|config| config.fee = fee);
+
+            self.emit_validator_set_fee_event(caller, fee);
+
+            return;
+        }
+
+        let max_increase_mapper = self.max_fee_increase();
+        if !max_increase_mapper.is_empty() {
+            require!(
+                fee - current_fee <= max_increase_mapper.get(),
+                "Fee increase too large"
+            );
+        }
 
-        self.emit_validator_set_fee_event(caller, fee);
+        require!(
+            self.pending_fee(caller_id).is_empty(),
+            "A fee increase is already pending"
+        );
+
+        let current_epoch = self.blockchain().get_block_epoch();
+        let fee_change_delay = self.fee_change_delay_epochs().get();
+        let last_announce_mapper = self.last_fee_announce_epoch(caller_id);
+        if !last_announce_mapper.is_empty() {
+            let min_interval = core::cmp::max(fee_change_delay, 1);
+            require!(
+                current_epoch >= last_announce_mapper.get() + min_interval,
+                "Fee increase announced too recently"
+            );
+        }
+        last_announce_mapper.set(current_epoch);
+
+        let effective_epoch = current_epoch + fee_change_delay;
+        self.pending_fee(caller_id).set(PendingFee {
+            fee,
+            announce_epoch: current_epoch,
+            effective_epoch,
+        });
+
+        self.emit_validator_announce_fee_event(caller, fee, effective_epoch);
     }
 
     #[endpoint(setMaxDelegation)]
     fn set_max_delegation(&self, max_delegation: BigUint) {
         let caller = self.blockchain().get_caller();
-        let caller_id = self.validator_id().get_id_non_zero(&caller);
+        let caller_id = self.resolve_validator_id_non_zero(&caller);
         self.validator_config(caller_id).update(|config| {
             let current_total = self.total_delegated_amount(caller_id).get();
             require!(max_delegation >= current_total, INVALID_MAX_AMOUNT_ERR_MSG);
@@ -151,43 +250,320 @@ pub trait ValidatorModule:
     #[endpoint(addOwnDelegation)]
     fn add_own_delegation(&self) {
         let validator = self.blockchain().get_caller();
-        let validator_id = self.validator_id().get_id_non_zero(&validator);
+        let validator_id = self.resolve_validator_id_non_zero(&validator);
+        self.require_validator_not_unregistering(validator_id);
+
         let user_id_of_validator = self.user_ids().get_id_or_insert(&validator);
         let validator_config = self.validator_config(validator_id).get();
 
         let payments = self.get_non_empty_payments();
-        let mut total = BigUint::zero();
-        for payment in &payments {
-            self.require_token_in_whitelist(&payment.token_identifier);
-
-            total += self.get_total_staked_egld(&payment.token_identifier, &payment.amount);
-        }
+        let total = self.get_whitelisted_payments_total(&payments);
 
         let args = AddDelegationArgs {
             total_delegated_mapper: self.total_delegated_amount(validator_id),
             total_by_user_mapper: self.total_by_user(user_id_of_validator, validator_id),
+            target_checkpoints_mapper: self.validator_stake_checkpoints(validator_id),
+            user_checkpoints_mapper: self
+                .user_validator_stake_checkpoints(user_id_of_validator, validator_id),
             all_delegators_mapper: &mut self.all_delegators(validator_id),
+            user_targets_mapper: &mut self.validators_of_user(user_id_of_validator),
             delegated_by_mapper: self.delegated_by(user_id_of_validator, validator_id),
             opt_max_delegation: validator_config.opt_max_delegation,
             payments_to_add: payments.clone(),
             total_amount: total,
+            target_id: validator_id,
             caller_id: user_id_of_validator,
         };
         self.add_delegation(args);
+        self.sync_validator_allocation_capacity(user_id_of_validator, validator_id);
+        self.add_total_deposited(&payments);
 
         self.emit_validator_add_own_delegation_event(validator, payments);
     }
 
+    /// To be called after every change of the tokens the user delegates to the validator
+    fn sync_validator_allocation_capacity(&self, user_id: AddressId, validator_id: AddressId) {
+        let delegated_tokens =
+            self.get_payments_or_default(&self.delegated_by(user_id, validator_id));
+        for sov_id in self.opted_in_sovs(user_id, validator_id).iter() {
+            let capacity =
+                self.sync_capacity_contribution(user_id, validator_id, sov_id, &delegated_tokens);
+            self.clamp_operator_allocation(validator_id, sov_id, &capacity);
+        }
+    }
+
+    fn clamp_operator_allocation(
+        &self,
+        validator_id: AddressId,
+        sov_id: AddressId,
+        capacity: &BigUint,
+    ) {
+        let allocation_mapper = self.operator_allocation(validator_id, sov_id);
+        let allocation = allocation_mapper.get();
+        if allocation <= *capacity {
+            return;
+        }
+
+        self.total_operator_allocated_sov(sov_id)
+            .update(|total| *total -= allocation - capacity);
+
+        if *capacity == 0 {
+            allocation_mapper.clear();
+            let _ = self.operator_sovs(validator_id).swap_remove(&sov_id);
+            let _ = self.all_sov_operators(sov_id).swap_remove(&validator_id);
+        } else {
+            allocation_mapper.set(capacity);
+        }
+
+        let validator = unsafe { self.get_validator_owner(validator_id).unwrap_unchecked() };
+        let sov_address = unsafe { self.get_sov_owner(sov_id).unwrap_unchecked() };
+        self.emit_validator_allocation_clamped_event(validator, sov_address, capacity.clone());
+    }
+
+    /// The announced fee becomes the validator's fee once its effective epoch is reached
+    fn apply_pending_fee(&self, validator_id: AddressId) {
+        let pending_fee_mapper = self.pending_fee(validator_id);
+        if pending_fee_mapper.is_empty() {
+            return;
+        }
+
+        let pending_fee = pending_fee_mapper.get();
+        let current_epoch = self.blockchain().get_block_epoch();
+        if current_epoch < pending_fee.effective_epoch {
+            return;
+        }
+
+        pending_fee_mapper.clear();
+        self.validator_config(validator_id)
+            .update(|config| config.fee = pending_fee.fee);
+    }
+
+    fn get_validator_fee(&self, validator_id: AddressId) -> Percent {
+        let pending_fee_mapper = self.pending_fee(validator_id);
+        if !pending_fee_mapper.is_empty() {
+            let pending_fee = pending_fee_mapper.get();
+            let current_epoch = self.blockchain().get_block_epoch();
+            if current_epoch >= pending_fee.effective_epoch {
+                return pending_fee.fee;
+            }
+        }
+
+        self.validator_config(validator_id).get().fee
+    }
+
+    fn is_in_fee_grace_window(&self, validator_id: AddressId) -> bool {
+        let pending_fee_mapper = self.pending_fee(validator_id);
+        if pending_fee_mapper.is_empty() {
+            return false;
+        }
+
+        let current_epoch = self.blockchain().get_block_epoch();
+
+        current_epoch < pending_fee_mapper.get().effective_epoch
+    }
+
+    /// IDs stay the same across ownership transfers, the current owner being tracked separately.
+    /// Validators never transferred are owned by their registration address.
+    fn resolve_validator_id(&self, address: &ManagedAddress) -> AddressId {
+        let owned_id = self.validator_id_of_owner(address).get();
+        if owned_id != NULL_ID {
+            return owned_id;
+        }
+
+        let validator_id = self.validator_id().get_id(address);
+        if validator_id == NULL_ID || !self.validator_owner(validator_id).is_empty() {
+            return NULL_ID;
+        }
+
+        validator_id
+    }
+
+    fn resolve_validator_id_non_zero(&self, address: &ManagedAddress) -> AddressId {
+        let validator_id = self.resolve_validator_id(address);
+        require!(validator_id != NULL_ID, "Unknown validator");
+
+        validator_id
+    }
+
+    fn get_validator_owner(&self, validator_id: AddressId) -> Option<ManagedAddress> {
+        let owner_mapper = self.validator_owner(validator_id);
+        if !owner_mapper.is_empty() {
+            return Some(owner_mapper.get());
+        }
+
+        self.validator_id().get_address(validator_id)
+    }
+
+    fn set_validator_owner(
+        &self,
+        validator_id: AddressId,
+        old_owner: &ManagedAddress,
+        new_owner: &ManagedAddress,
+    ) {
+        self.validator_id_of_owner(old_owner).clear();
+        self.validator_id_of_owner(new_owner).set(validator_id);
+        self.validator_owner(validator_id).set(new_owner);
+
+        // the old owner may register a new validator
+        let _ = self.validator_id().remove_by_address(old_owner);
+    }
+
+    fn is_registered_validator(&self, validator_id: AddressId) -> bool {
+        self.get_validator_owner(validator_id).is_some()
+    }
+
+    /// During the grace window of a fee increase, the stake delegated before the announcement
+    /// may be redelegated without the cooldown. Returns whether the amount is still covered.
+    fn try_use_fee_grace(
+        &self,
+        user_id: AddressId,
+        validator_id: AddressId,
+        amount: &BigUint,
+    ) -> bool {
+        if !self.is_in_fee_grace_window(validator_id) {
+            return false;
+        }
+
+        let announce_epoch = self.pending_fee(validator_id).get().announce_epoch;
+        if announce_epoch == 0 {
+            return false;
+        }
+
+        let pre_announcement_stake = self.get_stake_at_epoch(
+            &self.user_validator_stake_checkpoints(user_id, validator_id),
+            &self.total_by_user(user_id, validator_id),
+            announce_epoch,
+        );
+        let used_mapper = self.fee_grace_used(user_id, validator_id, announce_epoch);
+        let used = used_mapper.get() + amount;
+        if used > pre_announcement_stake {
+            return false;
+        }
+
+        used_mapper.set(used);
+
+        true
+    }
+
+    /// Keys already owned by another validator are dropped, and reported in an event
+    fn move_legacy_bls_keys(&self, validator_id: AddressId) {
+        let config_mapper = self.validator_config(validator_id);
+        let mut config = config_mapper.get();
+        if config.bls_keys.is_empty() {
+            return;
+        }
+
+        let mut bls_keys_mapper = self.bls_keys(validator_id);
+        let mut dropped_keys = ManagedVec::new();
+        for bls_key in config.bls_keys.iter() {
+            let key_owner_mapper = self.bls_key_owner(&bls_key);
+            if !key_owner_mapper.is_empty() {
+                if key_owner_mapper.get() != validator_id {
+                    dropped_keys.push((*bls_key).clone());
+                }
+
+                continue;
+            }
+
+            key_owner_mapper.set(validator_id);
+            self.bls_key_status(&bls_key).set(BlsKeyStatus::Pending);
+            let _ = bls_keys_mapper.insert((*bls_key).clone());
+        }
+
+        config.bls_keys = ManagedVec::new();
+        config_mapper.set(config);
+
+        if !dropped_keys.is_empty() {
+            let validator = unsafe { self.get_validator_owner(validator_id).unwrap_unchecked() };
+            self.emit_validator_drop_legacy_bls_keys_event(validator, dropped_keys);
+        }
+    }
+
+    fn require_validator_not_unregistering(&self, validator_id: AddressId) {
+        require!(
+            !self.validator_unregistering(validator_id).get(),
+            "Validator is unregistering"
+        );
+    }
+
     #[view(getValidatorConfig)]
-    fn get_validator_config(&self, address: ManagedAddress) -> ValidatorConfig<Self::Api> {
-        let validator_id = self.validator_id().get_id_non_zero(&address);
+    fn get_validator_config(
+        &self,
+        address: ManagedAddress,
+    ) -> OptionalValue<ValidatorConfig<Self::Api>> {
+        let validator_id = self.resolve_validator_id(&address);
+        if validator_id == NULL_ID {
+            return OptionalValue::None;
+        }
+
+        OptionalValue::Some(self.get_resolved_validator_config(validator_id))
+    }
+
+    /// The config with the fee currently charged and all the keys of the validator,
+    /// the ones not yet migrated included
+    fn get_resolved_validator_config(&self, validator_id: AddressId) -> ValidatorConfig<Self::Api> {
+        let mut config = self.validator_config(validator_id).get();
+        config.fee = self.get_validator_fee(validator_id);
+        for bls_key in self.bls_keys(validator_id).iter() {
+            config.bls_keys.push(bls_key);
+        }
 
-        self.validator_config(validator_id).get()
+        config
+    }
+
+    /// Returns the announced fee and the epoch it applies from, if not yet applied
+    #[view(getPendingFee)]
+    fn get_pending_fee(
+        &self,
+        address: ManagedAddress,
+    ) -> OptionalValue<MultiValue2<Percent, Epoch>> {
+        let validator_id = self.resolve_validator_id(&address);
+        if validator_id == NULL_ID || !self.is_in_fee_grace_window(validator_id) {
+            return OptionalValue::None;
+        }
+
+        let pending_fee = self.pending_fee(validator_id).get();
+
+        OptionalValue::Some((pending_fee.fee, pending_fee.effective_epoch).into())
+    }
+
+    #[view(getBlsKeys)]
+    fn get_bls_keys(&self, address: ManagedAddress) -> MultiValueEncoded<BlsKey<Self::Api>> {
+        let mut result = MultiValueEncoded::new();
+        let validator_id = self.resolve_validator_id(&address);
+        if validator_id == NULL_ID {
+            return result;
+        }
+
+        for bls_key in self.bls_keys(validator_id).iter() {
+            result.push(bls_key);
+        }
+        for bls_key in self.validator_config(validator_id).get().bls_keys.iter() {
+            result.push((*bls_key).clone());
+        }
+
+        result
+    }
+
+    #[view(getValidatorForBlsKey)]
+    fn get_validator_for_bls_key(
+        &self,
+        bls_key: BlsKey<Self::Api>,
+    ) -> OptionalValue<ManagedAddress> {
+        let key_owner_mapper = self.bls_key_owner(&bls_key);
+        if key_owner_mapper.is_empty() {
+            return OptionalValue::None;
+        }
+
+        self.get_validator_owner(key_owner_mapper.get()).into()
     }
 
     #[view(getTotalDelegatedAmount)]
     fn get_total_delegated_amount(&self, address: ManagedAddress) -> BigUint {
-        let validator_id = self.validator_id().get_id_non_zero(&address);
+        let validator_id = self.resolve_validator_id(&address);
+        if validator_id == NULL_ID {
+            return BigUint::zero();
+        }
 
         self.total_delegated_amount(validator_id).get()
     }
@@ -195,18 +571,73 @@ pub trait ValidatorModule:
     #[storage_mapper("validatorId")]
     fn validator_id(&self) -> AddressToIdMapper<Self::Api>;
 
+    #[storage_mapper("validatorOwner")]
+    fn validator_owner(&self, validator_id: AddressId) -> SingleValueMapper<ManagedAddress>;
+
+    #[storage_mapper("validatorIdOfOwner")]
+    fn validator_id_of_owner(&self, owner: &ManagedAddress) -> SingleValueMapper<AddressId>;
+
     #[storage_mapper("validatorConfig")]
     fn validator_config(
         &self,
         validator_id: AddressId,
     ) -> SingleValueMapper<ValidatorConfig<Self::Api>>;
 
+    #[storage_mapper("blsKeys")]
+    fn bls_keys(&self, validator_id: AddressId) -> UnorderedSetMapper<BlsKey<Self::Api>>;
+
+    /// Global index, so a key can only belong to a single validator
+    #[storage_mapper("blsKeyOwner")]
+    fn bls_key_owner(&self, bls_key: &BlsKey<Self::Api>) -> SingleValueMapper<AddressId>;
+
+    #[storage_mapper("blsKeyStatus")]
+    fn bls_key_status(&self, bls_key: &BlsKey<Self::Api>) -> SingleValueMapper<BlsKeyStatus>;
+
+    /// Jailed globally by the owner.
+    /// Jails by a sovereign chain don't count, they only keep the key out of that chain's security set.
+    fn is_key_jailed(&self, bls_key: &BlsKey<Self::Api>) -> bool {
+        self.bls_key_status(bls_key).get() == BlsKeyStatus::Jailed
+    }
+
+    /// Sovereign chains which jailed the key. The key stays out of their security set only,
+    /// even if it's removed and added again.
+    #[storage_mapper("blsKeyJailedForSov")]
+    fn bls_key_jailed_for_sov(&self, bls_key: &BlsKey<Self::Api>) -> UnorderedSetMapper<AddressId>;
+
     #[storage_mapper("idForName")]
     fn id_for_name(&self, name: &ManagedBuffer) -> SingleValueMapper<AddressId>;
 
+    #[storage_mapper("pendingFee")]
+    fn pending_fee(&self, validator_id: AddressId) -> SingleValueMapper<PendingFee>;
+
+    #[storage_mapper("lastFeeAnnounceEpoch")]
+    fn last_fee_announce_epoch(&self, validator_id: AddressId) -> SingleValueMapper<Epoch>;
+
+    #[storage_mapper("feeGraceUsed")]
+    fn fee_grace_used(
+        &self,
+        user_id: AddressId,
+        validator_id: AddressId,
+        announce_epoch: Epoch,
+    ) -> SingleValueMapper<BigUint>;
+
+    #[view(getFeeChangeDelayEpochs)]
+    #[storage_mapper("feeChangeDelayEpochs")]
+    fn fee_change_delay_epochs(&self) -> SingleValueMapper<Epoch>;
+
+    #[view(getMaxFeeIncrease)]
+    #[storage_mapper("maxFeeIncrease")]
+    fn max_fee_increase(&self) -> SingleValueMapper<Percent>;
+
+    #[storage_mapper("validatorUnregistering")]
+    fn validator_unregistering(&self, validator_id: AddressId) -> SingleValueMapper<bool>;
+
     #[storage_mapper("allDelegators")]
     fn all_delegators(&self, validator_id: AddressId) -> UnorderedSetMapper<AddressId>;
 
+    #[storage_mapper("validatorsOfUser")]
+    fn validators_of_user(&self, user_id: AddressId) -> UnorderedSetMapper<AddressId>;
+
     #[storage_mapper("delegatedBy")]
     fn delegated_by(
         &self,
@@ -223,4 +654,17 @@ pub trait ValidatorModule:
         user_id: AddressId,
         validator_id: AddressId,
     ) -> SingleValueMapper<BigUint>;
+
+    #[storage_mapper("validatorStakeCheckpoints")]
+    fn validator_stake_checkpoints(
+        &self,
+        validator_id: AddressId,
+    ) -> VecMapper<StakeCheckpoint<Self::Api>>;
+
+    #[storage_mapper("userValidatorStakeCheckpoints")]
+    fn user_validator_stake_checkpoints(
+        &self,
+        user_id: AddressId,
+        validator_id: AddressId,
+    ) -> VecMapper<StakeCheckpoint<Self::Api>>;
 }
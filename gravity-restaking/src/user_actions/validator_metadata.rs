@@ -0,0 +1,100 @@
+multiversx_sc::imports!();
+multiversx_sc::derive_imports!();
+
+pub const MAX_METADATA_LEN: usize = 512;
+
+#[derive(TypeAbi, TopEncode, TopDecode, NestedEncode, NestedDecode)]
+pub struct ValidatorMetadata<M: ManagedTypeApi> {
+    pub identity: ManagedBuffer<M>,
+    pub website: ManagedBuffer<M>,
+    pub description: ManagedBuffer<M>,
+    pub contact: ManagedBuffer<M>,
+    pub logo: ManagedBuffer<M>,
+}
+
+#[multiversx_sc::module]
+pub trait ValidatorMetadataModule:
+    crate::token_whitelist::TokenWhitelistModule
+    + super::validator::ValidatorModule
+    + super::common_actions::CommonActionsModule
+    + super::common_storage::CommonStorageModule
+    + crate::events::validator_events::ValidatorEventsModule
+    + utils::UtilsModule
+{
+    /// identity is the keybase identity of the validator, logo the URL of its avatar
+    #[endpoint(setValidatorMetadata)]
+    fn set_validator_metadata(
+        &self,
+        identity: ManagedBuffer,
+        website: ManagedBuffer,
+        description: ManagedBuffer,
+        contact: ManagedBuffer,
+        logo: ManagedBuffer,
+    ) {
+        require!(identity.len() <= MAX_METADATA_LEN, "Identity too long");
+        require!(website.len() <= MAX_METADATA_LEN, "Website too long");
+        require!(
+            description.len() <= MAX_METADATA_LEN,
+            "Description too long"
+        );
+        require!(contact.len() <= MAX_METADATA_LEN, "Contact too long");
+        require!(logo.len() <= MAX_METADATA_LEN, "Logo too long");
+
+        let caller = self.blockchain().get_caller();
+        let caller_id = self.resolve_validator_id_non_zero(&caller);
+        let metadata = ValidatorMetadata {
+            identity,
+            website,
+            description,
+            contact,
+            logo,
+        };
+        self.validator_metadata(caller_id).set(&metadata);
+
+        self.emit_validator_set_metadata_event(caller, metadata);
+    }
+
+    #[endpoint(changeName)]
+    fn change_name(&self, new_name: ManagedBuffer) {
+        self.require_not_empty_buffer(&new_name);
+        require!(new_name.len() <= MAX_METADATA_LEN, "Name too long");
+
+        let caller = self.blockchain().get_caller();
+        let caller_id = self.resolve_validator_id_non_zero(&caller);
+
+        let new_name_mapper = self.id_for_name(&new_name);
+        require!(new_name_mapper.is_empty(), "Name already taken");
+
+        self.validator_config(caller_id).update(|config| {
+            self.id_for_name(&config.name).clear();
+            config.name = new_name.clone();
+        });
+        new_name_mapper.set(caller_id);
+
+        self.emit_validator_change_name_event(caller, new_name);
+    }
+
+    #[view(getValidatorMetadata)]
+    fn get_validator_metadata(
+        &self,
+        address: ManagedAddress,
+    ) -> OptionalValue<ValidatorMetadata<Self::Api>> {
+        let validator_id = self.resolve_validator_id(&address);
+        if validator_id == NULL_ID {
+            return OptionalValue::None;
+        }
+
+        let mapper = self.validator_metadata(validator_id);
+        if mapper.is_empty() {
+            return OptionalValue::None;
+        }
+
+        OptionalValue::Some(mapper.get())
+    }
+
+    #[storage_mapper("validatorMetadata")]
+    fn validator_metadata(
+        &self,
+        validator_id: AddressId,
+    ) -> SingleValueMapper<ValidatorMetadata<Self::Api>>;
+}
@@ -0,0 +1,468 @@
+use crate::{
+    unique_payments::UniquePayments,
+    user_actions::{
+        sovereign::{Epoch, SovereignInfo},
+        unbond::UnbondInfo,
+        validator::ValidatorConfig,
+    },
+};
+
+multiversx_sc::imports!();
+multiversx_sc::derive_imports!();
+
+pub type ValidatorEntry<M> = MultiValue3<ManagedAddress<M>, ValidatorConfig<M>, BigUint<M>>;
+pub type SovereignEntry<M> = MultiValue3<ManagedAddress<M>, SovereignInfo<M>, BigUint<M>>;
+pub type DelegatorEntry<M> = MultiValue3<ManagedAddress<M>, UniquePayments<M>, BigUint<M>>;
+
+#[derive(TypeAbi, TopEncode, TopDecode)]
+pub struct Summary<M: ManagedTypeApi> {
+    pub nr_users: usize,
+    pub nr_validators: usize,
+    pub nr_sovereigns: usize,
+    pub total_delegated_validators: BigUint<M>,
+    pub total_delegated_sovereigns: BigUint<M>,
+    /// Value of all the tokens held for users, whether idle, delegated, locked or unbonding
+    pub tvl: BigUint<M>,
+}
+
+#[derive(TypeAbi, TopEncode, TopDecode, NestedEncode, NestedDecode, ManagedVecItem)]
+pub struct DelegationPosition<M: ManagedTypeApi> {
+    pub target: ManagedAddress<M>,
+    pub tokens: UniquePayments<M>,
+    pub egld_value: BigUint<M>,
+}
+
+#[derive(TypeAbi, TopEncode, TopDecode)]
+pub struct UserPortfolio<M: ManagedTypeApi> {
+    pub idle_tokens: UniquePayments<M>,
+    pub validator_delegations: ManagedVec<M, DelegationPosition<M>>,
+    pub sov_delegations: ManagedVec<M, DelegationPosition<M>>,
+    pub shared_stake: UniquePayments<M>,
+    pub shared_allocations: ManagedVec<M, DelegationPosition<M>>,
+    pub unbonding: ManagedVec<M, UnbondInfo<M>>,
+    pub delegation_rewards: BigUint<M>,
+}
+
+impl<M: ManagedTypeApi> Default for UserPortfolio<M> {
+    fn default() -> Self {
+        Self {
+            idle_tokens: UniquePayments::new(),
+            validator_delegations: ManagedVec::new(),
+            sov_delegations: ManagedVec::new(),
+            shared_stake: UniquePayments::new(),
+            shared_allocations: ManagedVec::new(),
+            unbonding: ManagedVec::new(),
+            delegation_rewards: BigUint::zero(),
+        }
+    }
+}
+
+#[multiversx_sc::module]
+pub trait ViewsModule:
+    crate::user_actions::user::UserModule
+    + crate::user_actions::shared_security::SharedSecurityModule
+    + crate::token_whitelist::TokenWhitelistModule
+    + crate::user_actions::validator::ValidatorModule
+    + crate::user_actions::sovereign::SovereignModule
+    + crate::user_actions::unbond::UnbondModule
+    + crate::user_actions::common_actions::CommonActionsModule
+    + crate::user_actions::common_storage::CommonStorageModule
+    + crate::user_actions::delegation_rewards::DelegationRewardsModule
+    + crate::events::user_events::UserEventsModule
+    + crate::events::validator_events::ValidatorEventsModule
+    + crate::events::sov_events::SovEventsModule
+    + utils::UtilsModule
+{
+    /// Triplets of (address, config, total_delegated)
+    #[view(getValidators)]
+    fn get_validators(
+        &self,
+        offset: usize,
+        limit: usize,
+    ) -> MultiValueEncoded<ValidatorEntry<Self::Api>> {
+        let mut result = MultiValueEncoded::new();
+        let last_id = self.validator_id().get_last_id();
+        let mut skipped = 0;
+        let mut nr_entries = 0;
+        for validator_id in 1..=last_id {
+            if nr_entries == limit {
+                break;
+            }
+
+            let opt_address = self.get_validator_owner(validator_id);
+            if opt_address.is_none() {
+                continue;
+            }
+            if skipped < offset {
+                skipped += 1;
+
+                continue;
+            }
+
+            let address = unsafe { opt_address.unwrap_unchecked() };
+            let config = self.get_resolved_validator_config(validator_id);
+            let total_delegated = self.total_delegated_amount(validator_id).get();
+            result.push((address, config, total_delegated).into());
+            nr_entries += 1;
+        }
+
+        result
+    }
+
+    /// Triplets of (address, info, total_delegated), operator allocations included in the total
+    #[view(getSovereigns)]
+    fn get_sovereigns(
+        &self,
+        offset: usize,
+        limit: usize,
+    ) -> MultiValueEncoded<SovereignEntry<Self::Api>> {
+        let mut result = MultiValueEncoded::new();
+        let last_id = self.sov_id().get_last_id();
+        let mut skipped = 0;
+        let mut nr_entries = 0;
+        for sov_id in 1..=last_id {
+            if nr_entries == limit {
+                break;
+            }
+
+            let opt_address = self.get_sov_owner(sov_id);
+            if opt_address.is_none() {
+                continue;
+            }
+            if skipped < offset {
+                skipped += 1;
+
+                continue;
+            }
+
+            let address = unsafe { opt_address.unwrap_unchecked() };
+            let info = self.sov_info(sov_id).get();
+            let total_delegated = self.get_total_sov_security(sov_id);
+            result.push((address, info, total_delegated).into());
+            nr_entries += 1;
+        }
+
+        result
+    }
+
+    /// Triplets of (address, tokens, total_delegated)
+    #[view(getValidatorDelegators)]
+    fn get_validator_delegators(
+        &self,
+        validator: ManagedAddress,
+        offset: usize,
+        limit: usize,
+    ) -> MultiValueEncoded<DelegatorEntry<Self::Api>> {
+        let mut result = MultiValueEncoded::new();
+        let validator_id = self.resolve_validator_id(&validator);
+        if validator_id == NULL_ID {
+            return result;
+        }
+
+        let delegators_mapper = self.all_delegators(validator_id);
+        let len = delegators_mapper.len();
+        let end = core::cmp::min(len, offset.saturating_add(limit));
+        for i in offset..end {
+            let user_id = delegators_mapper.get_by_index(i + 1);
+            let address = unsafe { self.user_ids().get_address(user_id).unwrap_unchecked() };
+            let tokens = self.get_payments_or_default(&self.delegated_by(user_id, validator_id));
+            let total = self.total_by_user(user_id, validator_id).get();
+            result.push((address, tokens, total).into());
+        }
+
+        result
+    }
+
+    /// Triplets of (address, tokens, total_delegated)
+    #[view(getSovDelegators)]
+    fn get_sov_delegators(
+        &self,
+        sov_address: ManagedAddress,
+        offset: usize,
+        limit: usize,
+    ) -> MultiValueEncoded<DelegatorEntry<Self::Api>> {
+        let mut result = MultiValueEncoded::new();
+        let sov_id = self.resolve_sov_id(&sov_address);
+        if sov_id == NULL_ID {
+            return result;
+        }
+
+        let delegators_mapper = self.all_sov_delegators(sov_id);
+        let len = delegators_mapper.len();
+        let end = core::cmp::min(len, offset.saturating_add(limit));
+        for i in offset..end {
+            let user_id = delegators_mapper.get_by_index(i + 1);
+            let address = unsafe { self.user_ids().get_address(user_id).unwrap_unchecked() };
+            let tokens = self.get_payments_or_default(&self.delegated_sov_by(user_id, sov_id));
+            let total = self.total_sov_by_user(user_id, sov_id).get();
+            result.push((address, tokens, total).into());
+        }
+
+        result
+    }
+
+    #[view(getSummary)]
+    fn get_summary(&self) -> Summary<Self::Api> {
+        let mut nr_validators = 0;
+        let mut total_delegated_validators = BigUint::zero();
+        for validator_id in 1..=self.validator_id().get_last_id() {
+            if !self.is_registered_validator(validator_id) {
+                continue;
+            }
+
+            nr_validators += 1;
+            total_delegated_validators += self.total_delegated_amount(validator_id).get();
+        }
+
+        let mut nr_sovereigns = 0;
+        let mut total_delegated_sovereigns = BigUint::zero();
+        for sov_id in 1..=self.sov_id().get_last_id() {
+            if !self.is_registered_sov(sov_id) {
+                continue;
+            }
+
+            nr_sovereigns += 1;
+            total_delegated_sovereigns += self.get_total_sov_security(sov_id);
+        }
+
+        let mut tvl = BigUint::zero();
+        let total_deposited = self.get_payments_or_default(&self.total_deposited());
+        for payment in &total_deposited.into_payments() {
+            tvl += self.get_total_staked_egld(&payment.token_identifier, &payment.amount);
+        }
+
+        // the contract holds the tokenized positions under its own user ID
+        let own_address = self.blockchain().get_sc_address();
+        let mut nr_users = self.user_ids().get_last_id() as usize;
+        if self.user_ids().get_id(&own_address) != NULL_ID {
+            nr_users -= 1;
+        }
+
+        Summary {
+            nr_users,
+            nr_validators,
+            nr_sovereigns,
+            total_delegated_validators,
+            total_delegated_sovereigns,
+            tvl,
+        }
+    }
+
+    /// Empty for unknown users.
+    /// Delegations made before targets were tracked per user need a backfillUserTargets call first.
+    #[view(getUserPortfolio)]
+    fn get_user_portfolio(&self, user: ManagedAddress) -> UserPortfolio<Self::Api> {
+        let user_id = self.user_ids().get_id(&user);
+        if user_id == NULL_ID {
+            return UserPortfolio::default();
+        }
+
+        let mut validator_delegations = ManagedVec::new();
+        for validator_id in self.validators_of_user(user_id).iter() {
+            let position = DelegationPosition {
+                target: unsafe { self.get_validator_owner(validator_id).unwrap_unchecked() },
+                tokens: self.get_payments_or_default(&self.delegated_by(user_id, validator_id)),
+                egld_value: self.total_by_user(user_id, validator_id).get(),
+            };
+            validator_delegations.push(position);
+        }
+
+        let mut sov_delegations = ManagedVec::new();
+        for sov_id in self.sovs_of_user(user_id).iter() {
+            let position = DelegationPosition {
+                target: unsafe { self.get_sov_owner(sov_id).unwrap_unchecked() },
+                tokens: self.get_payments_or_default(&self.delegated_sov_by(user_id, sov_id)),
+                egld_value: self.total_sov_by_user(user_id, sov_id).get(),
+            };
+            sov_delegations.push(position);
+        }
+
+        let mut shared_allocations = ManagedVec::new();
+        for sov_id in self.shared_sovs_of_user(user_id).iter() {
+            let position = DelegationPosition {
+                target: unsafe { self.get_sov_owner(sov_id).unwrap_unchecked() },
+                tokens: self.get_payments_or_default(&self.shared_sov_allocation(user_id, sov_id)),
+                egld_value: self.total_shared_sov_by_user(user_id, sov_id).get(),
+            };
+            shared_allocations.push(position);
+        }
+
+        let unbond_mapper = self.unbond_info(user_id);
+        let unbonding = if !unbond_mapper.is_empty() {
+            unbond_mapper.get()
+        } else {
+            ManagedVec::new()
+        };
+
+        let mut delegation_rewards = BigUint::zero();
+        for delegation in self.delegation_contracts_of_user(user_id).iter() {
+            delegation_rewards += self.get_pending_delegation_rewards_by_id(&delegation, user_id);
+        }
+
+        UserPortfolio {
+            idle_tokens: self.get_payments_or_default(&self.user_tokens(user_id)),
+            validator_delegations,
+            sov_delegations,
+            shared_stake: self.get_payments_or_default(&self.shared_stake(user_id)),
+            shared_allocations,
+            unbonding,
+            delegation_rewards,
+        }
+    }
+
+    #[view(getUserTokensByAddress)]
+    fn get_user_tokens_by_address(&self, user: ManagedAddress) -> UniquePayments<Self::Api> {
+        let user_id = self.user_ids().get_id(&user);
+        if user_id == NULL_ID {
+            return UniquePayments::new();
+        }
+
+        self.get_payments_or_default(&self.user_tokens(user_id))
+    }
+
+    #[view(getDelegatedToValidator)]
+    fn get_delegated_to_validator(
+        &self,
+        user: ManagedAddress,
+        validator: ManagedAddress,
+    ) -> UniquePayments<Self::Api> {
+        let user_id = self.user_ids().get_id(&user);
+        let validator_id = self.resolve_validator_id(&validator);
+        if user_id == NULL_ID || validator_id == NULL_ID {
+            return UniquePayments::new();
+        }
+
+        self.get_payments_or_default(&self.delegated_by(user_id, validator_id))
+    }
+
+    #[view(getDelegatedToSov)]
+    fn get_delegated_to_sov(
+        &self,
+        user: ManagedAddress,
+        sov_address: ManagedAddress,
+    ) -> UniquePayments<Self::Api> {
+        let user_id = self.user_ids().get_id(&user);
+        let sov_id = self.resolve_sov_id(&sov_address);
+        if user_id == NULL_ID || sov_id == NULL_ID {
+            return UniquePayments::new();
+        }
+
+        self.get_payments_or_default(&self.delegated_sov_by(user_id, sov_id))
+    }
+
+    #[view(getValidatorStakeAtEpoch)]
+    fn get_validator_stake_at_epoch(&self, validator: ManagedAddress, epoch: Epoch) -> BigUint {
+        let validator_id = self.resolve_validator_id(&validator);
+        if validator_id == NULL_ID {
+            return BigUint::zero();
+        }
+
+        self.get_stake_at_epoch(
+            &self.validator_stake_checkpoints(validator_id),
+            &self.total_delegated_amount(validator_id),
+            epoch,
+        )
+    }
+
+    #[view(getSovStakeAtEpoch)]
+    fn get_sov_stake_at_epoch(&self, sov_address: ManagedAddress, epoch: Epoch) -> BigUint {
+        let sov_id = self.resolve_sov_id(&sov_address);
+        if sov_id == NULL_ID {
+            return BigUint::zero();
+        }
+
+        self.get_stake_at_epoch(
+            &self.sov_stake_checkpoints(sov_id),
+            &self.total_delegated_sov_amount(sov_id),
+            epoch,
+        )
+    }
+
+    #[view(getUserValidatorStakeAtEpoch)]
+    fn get_user_validator_stake_at_epoch(
+        &self,
+        user: ManagedAddress,
+        validator: ManagedAddress,
+        epoch: Epoch,
+    ) -> BigUint {
+        let user_id = self.user_ids().get_id(&user);
+        let validator_id = self.resolve_validator_id(&validator);
+        if user_id == NULL_ID || validator_id == NULL_ID {
+            return BigUint::zero();
+        }
+
+        self.get_stake_at_epoch(
+            &self.user_validator_stake_checkpoints(user_id, validator_id),
+            &self.total_by_user(user_id, validator_id),
+            epoch,
+        )
+    }
+
+    /// Includes the user's shared security allocation
+    #[view(getUserSovStakeAtEpoch)]
+    fn get_user_sov_stake_at_epoch(
+        &self,
+        user: ManagedAddress,
+        sov_address: ManagedAddress,
+        epoch: Epoch,
+    ) -> BigUint {
+        let user_id = self.user_ids().get_id(&user);
+        let sov_id = self.resolve_sov_id(&sov_address);
+        if user_id == NULL_ID || sov_id == NULL_ID {
+            return BigUint::zero();
+        }
+
+        let delegated = self.get_stake_at_epoch(
+            &self.user_sov_stake_checkpoints(user_id, sov_id),
+            &self.total_sov_by_user(user_id, sov_id),
+            epoch,
+        );
+        let shared = self.get_stake_at_epoch(
+            &self.user_shared_sov_stake_checkpoints(user_id, sov_id),
+            &self.total_shared_sov_by_user(user_id, sov_id),
+            epoch,
+        );
+
+        delegated + shared
+    }
+
+    /// Returns 0 for unknown addresses
+    #[view(getUserId)]
+    fn get_user_id(&self, address: ManagedAddress) -> AddressId {
+        self.user_ids().get_id(&address)
+    }
+
+    #[view(getUserAddress)]
+    fn get_user_address(&self, user_id: AddressId) -> OptionalValue<ManagedAddress> {
+        self.user_ids().get_address(user_id).into()
+    }
+
+    /// Returns 0 for unknown addresses
+    #[view(getValidatorId)]
+    fn get_validator_id(&self, address: ManagedAddress) -> AddressId {
+        self.resolve_validator_id(&address)
+    }
+
+    #[view(getValidatorAddress)]
+    fn get_validator_address(&self, validator_id: AddressId) -> OptionalValue<ManagedAddress> {
+        self.get_validator_owner(validator_id).into()
+    }
+
+    /// Returns 0 for unknown addresses
+    #[view(getSovId)]
+    fn get_sov_id(&self, address: ManagedAddress) -> AddressId {
+        self.resolve_sov_id(&address)
+    }
+
+    #[view(getSovAddress)]
+    fn get_sov_address(&self, sov_id: AddressId) -> OptionalValue<ManagedAddress> {
+        self.get_sov_owner(sov_id).into()
+    }
+
+    #[view(getSovAddressForName)]
+    fn get_sov_address_for_name(&self, name: ManagedBuffer) -> OptionalValue<ManagedAddress> {
+        let sov_id = self.sov_chain_for_name(&name).get();
+
+        self.get_sov_owner(sov_id).into()
+    }
+}
@@ -0,0 +1,230 @@
+mod restaking_setup;
+
+use gravity_restaking::{
+    token_whitelist::TokenWhitelistModule,
+    treasury::TreasuryModule,
+    user_actions::{
+        call_delegation::{CallDelegationModule, EGLD_TOKEN_ID},
+        common_storage::CommonStorageModule,
+        delegation_rewards::DelegationRewardsModule,
+        user::{PaymentsMultiValue, UserModule},
+    },
+};
+use multiversx_sc::{
+    codec::multi_types::OptionalValue,
+    types::{Address, ManagedAsyncCallResult, MultiValue3, MultiValueEncoded, TokenIdentifier},
+};
+use multiversx_sc_scenario::{
+    managed_address, managed_biguint, managed_token_id, rust_biguint, DebugApi,
+};
+use restaking_setup::*;
+
+const MOVED_STAKE: u64 = 400;
+const OTHER_MOVED_STAKE: u64 = 100;
+const REWARDS: u64 = 100;
+const DELEGATION_BALANCE: u64 = 1_000;
+
+struct DelegationSetup<ContractObjBuilder>
+where
+    ContractObjBuilder: 'static + Copy + Fn() -> gravity_restaking::ContractObj<DebugApi>,
+{
+    setup: RestakingSetup<ContractObjBuilder>,
+    // stands in for the delegation contract, whose callbacks are called directly
+    delegation: Address,
+}
+
+impl<ContractObjBuilder> DelegationSetup<ContractObjBuilder>
+where
+    ContractObjBuilder: 'static + Copy + Fn() -> gravity_restaking::ContractObj<DebugApi>,
+{
+    fn new(builder: ContractObjBuilder) -> Self {
+        let mut setup = RestakingSetup::new(builder);
+        let delegation = setup
+            .b_mock
+            .create_user_account(&rust_biguint!(DELEGATION_BALANCE));
+        setup.owner_call(|sc| {
+            sc.add_token_to_whitelist(
+                managed_token_id!(EGLD_TOKEN_ID),
+                managed_biguint!(ONE_TOKEN_IN_EGLD),
+                OptionalValue::None,
+            );
+        });
+
+        Self { setup, delegation }
+    }
+
+    fn move_stake(&mut self, user: &Address, amount: u64) {
+        let delegation = self.delegation.clone();
+        self.setup
+            .call(&delegation, |sc| {
+                sc.move_stake_callback(
+                    managed_address!(user),
+                    managed_biguint!(amount),
+                    ManagedAsyncCallResult::Ok(()),
+                );
+            })
+            .assert_ok();
+    }
+
+    fn claim(&mut self, rewards: u64) {
+        let delegation = self.delegation.clone();
+        self.setup
+            .b_mock
+            .execute_tx(
+                &delegation,
+                &self.setup.sc_wrapper,
+                &rust_biguint!(rewards),
+                |sc| {
+                    sc.claim_rewards_callback(
+                        managed_address!(&delegation),
+                        ManagedAsyncCallResult::Ok(()),
+                    );
+                },
+            )
+            .assert_ok();
+    }
+
+    fn check_rewards(&mut self, user: &Address, expected_rewards: u64) {
+        let delegation = self.delegation.clone();
+        self.setup.query(|sc| {
+            assert_eq!(
+                sc.get_pending_delegation_rewards(
+                    managed_address!(user),
+                    managed_address!(&delegation)
+                ),
+                managed_biguint!(expected_rewards)
+            );
+        });
+    }
+
+    fn check_protocol_rewards(&mut self, expected_rewards: u64) {
+        self.setup.query(|sc| {
+            let egld_token_id = TokenIdentifier::from_esdt_bytes(EGLD_TOKEN_ID);
+            assert_eq!(
+                sc.get_accrued_protocol_fees().get_amount(&egld_token_id, 0),
+                managed_biguint!(expected_rewards)
+            );
+        });
+    }
+}
+
+fn egld_tokens(amount: u64) -> PaymentsMultiValue<DebugApi> {
+    let mut tokens = MultiValueEncoded::new();
+    tokens.push(MultiValue3::from((
+        managed_token_id!(EGLD_TOKEN_ID),
+        0u64,
+        managed_biguint!(amount),
+    )));
+
+    tokens
+}
+
+#[test]
+fn restaked_moved_stake_keeps_earning_test() {
+    let mut delegation_setup = DelegationSetup::new(gravity_restaking::contract_obj);
+    let user = delegation_setup.setup.user.clone();
+    let validator = delegation_setup.setup.validator.clone();
+    delegation_setup.move_stake(&user, MOVED_STAKE);
+    delegation_setup
+        .setup
+        .user_call(|sc| {
+            sc.delegate_to_validator(managed_address!(&validator), egld_tokens(MOVED_STAKE));
+        })
+        .assert_ok();
+
+    // the first claim paid for the time before the stake arrived
+    delegation_setup.claim(REWARDS);
+    delegation_setup.check_rewards(&user, 0);
+    delegation_setup.check_protocol_rewards(REWARDS);
+
+    delegation_setup.claim(REWARDS);
+    delegation_setup.check_rewards(&user, REWARDS);
+    delegation_setup.check_protocol_rewards(REWARDS);
+
+    // withdrawn EGLD stops earning, its share goes to the protocol
+    delegation_setup
+        .setup
+        .user_call(|sc| {
+            sc.revoke_delegation_from_validator(
+                managed_address!(&validator),
+                egld_tokens(MOVED_STAKE / 2),
+            );
+        })
+        .assert_ok();
+    delegation_setup.setup.b_mock.set_block_epoch(UNBOND_EPOCHS);
+    delegation_setup
+        .setup
+        .user_call(|sc| {
+            sc.unbond_tokens_gravity_restaking();
+            sc.withdraw(egld_tokens(MOVED_STAKE / 2));
+        })
+        .assert_ok();
+
+    delegation_setup.claim(REWARDS);
+    delegation_setup.check_rewards(&user, REWARDS + REWARDS / 2);
+    delegation_setup.check_protocol_rewards(REWARDS + REWARDS / 2);
+}
+
+#[test]
+fn pending_moved_stake_share_goes_to_the_protocol_test() {
+    let mut delegation_setup = DelegationSetup::new(gravity_restaking::contract_obj);
+    let user = delegation_setup.setup.user.clone();
+    let other_user = delegation_setup
+        .setup
+        .b_mock
+        .create_user_account(&rust_biguint!(0));
+    delegation_setup.move_stake(&user, MOVED_STAKE);
+    delegation_setup.claim(REWARDS);
+    delegation_setup.check_protocol_rewards(REWARDS);
+
+    // the stake moved in before the claim shares it, but can't be attributed yet
+    delegation_setup.move_stake(&other_user, OTHER_MOVED_STAKE);
+    delegation_setup.claim(REWARDS);
+    delegation_setup.check_rewards(&user, 80);
+    delegation_setup.check_rewards(&other_user, 0);
+    delegation_setup.check_protocol_rewards(REWARDS + 20);
+
+    delegation_setup.claim(REWARDS);
+    delegation_setup.check_rewards(&user, 160);
+    delegation_setup.check_rewards(&other_user, 20);
+    delegation_setup.check_protocol_rewards(REWARDS + 20);
+}
+
+#[test]
+fn compounded_rewards_only_count_as_moved_stake_test() {
+    let mut delegation_setup = DelegationSetup::new(gravity_restaking::contract_obj);
+    let user = delegation_setup.setup.user.clone();
+    let delegation = delegation_setup.delegation.clone();
+    delegation_setup.move_stake(&user, MOVED_STAKE);
+    delegation_setup.claim(0);
+    delegation_setup.claim(REWARDS);
+
+    delegation_setup
+        .setup
+        .user_call(|sc| {
+            let user_id = sc.user_ids().get_id(&managed_address!(&user));
+            let rewards = sc.take_delegation_rewards(&managed_address!(&delegation), user_id);
+            assert_eq!(rewards, managed_biguint!(REWARDS));
+
+            sc.compound_rewards_callback(
+                managed_address!(&user),
+                managed_address!(&delegation),
+                rewards,
+                ManagedAsyncCallResult::Ok(()),
+            );
+        })
+        .assert_ok();
+
+    delegation_setup.setup.query(|sc| {
+        let user_id = sc.user_ids().get_id(&managed_address!(&user));
+        let egld_token_id = TokenIdentifier::from_esdt_bytes(EGLD_TOKEN_ID);
+        assert_eq!(
+            sc.user_tokens(user_id).get().get_amount(&egld_token_id, 0),
+            managed_biguint!(MOVED_STAKE)
+        );
+        assert_eq!(
+            sc.get_moved_stake(managed_address!(&user), managed_address!(&delegation)),
+            managed_biguint!(MOVED_STAKE + REWARDS)
+        );
+    });
+}
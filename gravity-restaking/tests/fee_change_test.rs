@@ -0,0 +1,140 @@
+mod restaking_setup;
+
+use gravity_restaking::user_actions::{
+    redelegate::RedelegateModule, user::UserModule, validator::ValidatorModule,
+};
+use multiversx_sc::types::Address;
+use multiversx_sc_scenario::{managed_address, managed_buffer, rust_biguint, DebugApi};
+use restaking_setup::*;
+
+static OTHER_VALIDATOR_NAME: &[u8] = b"other-validator";
+
+const FEE_CHANGE_DELAY_EPOCHS: u64 = 5;
+const MAX_FEE_INCREASE: u32 = 1_000;
+const COOLDOWN_EPOCHS: u64 = 10;
+
+fn setup_with_fee_limits<ContractObjBuilder>(
+    builder: ContractObjBuilder,
+) -> RestakingSetup<ContractObjBuilder>
+where
+    ContractObjBuilder: 'static + Copy + Fn() -> gravity_restaking::ContractObj<DebugApi>,
+{
+    let mut setup = RestakingSetup::new(builder);
+    setup.owner_call(|sc| {
+        sc.set_fee_change_delay_epochs(FEE_CHANGE_DELAY_EPOCHS);
+        sc.set_max_fee_increase(MAX_FEE_INCREASE);
+        sc.set_redelegation_cooldown_epochs(COOLDOWN_EPOCHS);
+    });
+
+    setup
+}
+
+fn check_fee<ContractObjBuilder>(setup: &mut RestakingSetup<ContractObjBuilder>, expected_fee: u32)
+where
+    ContractObjBuilder: 'static + Copy + Fn() -> gravity_restaking::ContractObj<DebugApi>,
+{
+    let validator = setup.validator.clone();
+    setup.query(|sc| {
+        let config = sc
+            .get_validator_config(managed_address!(&validator))
+            .into_option()
+            .unwrap();
+        assert_eq!(config.fee, expected_fee);
+    });
+}
+
+fn redelegate<ContractObjBuilder>(
+    setup: &mut RestakingSetup<ContractObjBuilder>,
+    from: &Address,
+    to: &Address,
+    amount: u64,
+) -> multiversx_sc_scenario::whitebox_legacy::TxResult
+where
+    ContractObjBuilder: 'static + Copy + Fn() -> gravity_restaking::ContractObj<DebugApi>,
+{
+    setup.user_call(|sc| {
+        sc.redelegate_validator(
+            managed_address!(from),
+            managed_address!(to),
+            stake_tokens(amount),
+        );
+    })
+}
+
+#[test]
+fn fee_increase_applies_after_delay_test() {
+    let mut setup = setup_with_fee_limits(gravity_restaking::contract_obj);
+    let validator = setup.validator.clone();
+    setup
+        .call(&validator, |sc| sc.set_up_fee(MAX_FEE_INCREASE + 1))
+        .assert_user_error("Fee increase too large");
+    setup
+        .call(&validator, |sc| sc.set_up_fee(MAX_FEE_INCREASE / 2))
+        .assert_ok();
+    check_fee(&mut setup, 0);
+
+    setup
+        .call(&validator, |sc| sc.set_up_fee(MAX_FEE_INCREASE))
+        .assert_user_error("A fee increase is already pending");
+    setup.query(|sc| {
+        let (fee, effective_epoch) = sc
+            .get_pending_fee(managed_address!(&validator))
+            .into_option()
+            .unwrap()
+            .into_tuple();
+        assert_eq!(fee, MAX_FEE_INCREASE / 2);
+        assert_eq!(effective_epoch, FEE_CHANGE_DELAY_EPOCHS);
+    });
+
+    setup.b_mock.set_block_epoch(FEE_CHANGE_DELAY_EPOCHS);
+    check_fee(&mut setup, MAX_FEE_INCREASE / 2);
+    setup.query(|sc| {
+        assert!(sc
+            .get_pending_fee(managed_address!(&validator))
+            .into_option()
+            .is_none());
+    });
+
+    setup.call(&validator, |sc| sc.set_up_fee(0)).assert_ok();
+    check_fee(&mut setup, 0);
+}
+
+#[test]
+fn fee_grace_only_covers_stake_from_before_the_announcement_test() {
+    let mut setup = setup_with_fee_limits(gravity_restaking::contract_obj);
+    let validator = setup.validator.clone();
+    let other_validator = setup.b_mock.create_user_account(&rust_biguint!(0));
+    setup
+        .call(&other_validator, |sc| {
+            sc.register(managed_buffer!(OTHER_VALIDATOR_NAME));
+        })
+        .assert_ok();
+
+    setup.b_mock.set_block_epoch(1);
+    setup
+        .user_call(|sc| {
+            sc.delegate_to_validator(
+                managed_address!(&other_validator),
+                stake_tokens(USER_BALANCE),
+            );
+        })
+        .assert_ok();
+    redelegate(&mut setup, &other_validator, &validator, USER_BALANCE).assert_ok();
+
+    setup.b_mock.set_block_epoch(2);
+    setup
+        .call(&validator, |sc| sc.set_up_fee(MAX_FEE_INCREASE))
+        .assert_ok();
+
+    setup.b_mock.set_block_epoch(3);
+    redelegate(&mut setup, &validator, &other_validator, 600).assert_ok();
+
+    // above the stake delegated before the announcement, the cooldown at the validator applies
+    redelegate(&mut setup, &validator, &other_validator, 500)
+        .assert_user_error("Redelegation cooldown not finished");
+    redelegate(&mut setup, &validator, &other_validator, 400).assert_ok();
+
+    // the grace does not waive the cooldown at the destination
+    redelegate(&mut setup, &other_validator, &validator, 100)
+        .assert_user_error("Redelegation cooldown not finished");
+}
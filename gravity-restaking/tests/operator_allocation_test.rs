@@ -0,0 +1,193 @@
+mod restaking_setup;
+
+use gravity_restaking::{
+    token_whitelist::TokenWhitelistModule,
+    user_actions::{
+        allocation_capacity::AllocationCapacityModule,
+        common_storage::CommonStorageModule,
+        deregistration::DeregistrationModule,
+        operator_allocation::OperatorAllocationModule,
+        sovereign::{SovState, SovereignModule},
+        user::UserModule,
+        validator::ValidatorModule,
+    },
+    views::ViewsModule,
+};
+use multiversx_sc::{codec::multi_types::OptionalValue, types::MultiValueEncoded};
+use multiversx_sc_scenario::{
+    managed_address, managed_biguint, managed_buffer, managed_token_id, DebugApi,
+};
+use restaking_setup::*;
+
+static OTHER_TOKEN_ID: &[u8] = b"OTHER-123456";
+
+const OPT_IN_PERCENT: u32 = 5_000;
+const OPTED_IN_STAKE: u64 = USER_BALANCE / 2;
+
+fn setup_with_opt_in<ContractObjBuilder>(
+    builder: ContractObjBuilder,
+) -> RestakingSetup<ContractObjBuilder>
+where
+    ContractObjBuilder: 'static + Copy + Fn() -> gravity_restaking::ContractObj<DebugApi>,
+{
+    let mut setup = RestakingSetup::new(builder);
+    let validator = setup.validator.clone();
+    setup
+        .user_call(|sc| {
+            sc.delegate_to_validator(managed_address!(&validator), stake_tokens(USER_BALANCE));
+        })
+        .assert_ok();
+    setup
+        .user_call(|sc| {
+            sc.set_allocation_opt_in(
+                managed_address!(&validator),
+                managed_buffer!(SOV_NAME),
+                OPT_IN_PERCENT,
+            );
+        })
+        .assert_ok();
+
+    setup
+}
+
+fn allocate_to_sov<ContractObjBuilder>(
+    setup: &mut RestakingSetup<ContractObjBuilder>,
+    amount: u64,
+) -> multiversx_sc_scenario::whitebox_legacy::TxResult
+where
+    ContractObjBuilder: 'static + Copy + Fn() -> gravity_restaking::ContractObj<DebugApi>,
+{
+    let validator = setup.validator.clone();
+    setup.call(&validator, |sc| {
+        sc.allocate_to_sovereign(managed_buffer!(SOV_NAME), managed_biguint!(amount));
+    })
+}
+
+fn check_allocation<ContractObjBuilder>(
+    setup: &mut RestakingSetup<ContractObjBuilder>,
+    expected_capacity: u64,
+    expected_allocation: u64,
+) where
+    ContractObjBuilder: 'static + Copy + Fn() -> gravity_restaking::ContractObj<DebugApi>,
+{
+    let validator = setup.validator.clone();
+    let sov = setup.sov.clone();
+    setup.query(|sc| {
+        assert_eq!(
+            sc.get_allocation_capacity(managed_address!(&validator), managed_address!(&sov)),
+            managed_biguint!(expected_capacity)
+        );
+
+        let mut allocation = managed_biguint!(0);
+        for entry in sc.get_operator_allocations(managed_address!(&validator)) {
+            let (sov_address, amount) = entry.into_tuple();
+            assert_eq!(sov_address, managed_address!(&sov));
+            allocation = amount;
+        }
+        assert_eq!(allocation, managed_biguint!(expected_allocation));
+    });
+}
+
+#[test]
+fn operator_allocation_counts_towards_sov_security_test() {
+    let mut setup = setup_with_opt_in(gravity_restaking::contract_obj);
+    let sov = setup.sov.clone();
+    setup
+        .call(&sov, |sc| {
+            sc.set_min_total_security(managed_biguint!(OPTED_IN_STAKE));
+        })
+        .assert_ok();
+
+    allocate_to_sov(&mut setup, OPTED_IN_STAKE + 1).assert_user_error("Not enough opted-in stake");
+    allocate_to_sov(&mut setup, OPTED_IN_STAKE).assert_ok();
+    check_allocation(&mut setup, OPTED_IN_STAKE, OPTED_IN_STAKE);
+
+    setup.query(|sc| {
+        assert!(sc.has_min_total_security(managed_address!(&sov)));
+        assert!(matches!(
+            sc.get_sov_state(managed_address!(&sov)),
+            OptionalValue::Some(SovState::Active)
+        ));
+
+        for entry in sc.get_sovereigns(0, 10) {
+            let (address, _, total_delegated) = entry.into_tuple();
+            if address == managed_address!(&sov) {
+                assert_eq!(total_delegated, managed_biguint!(OPTED_IN_STAKE));
+            }
+        }
+        assert_eq!(
+            sc.get_summary().total_delegated_sovereigns,
+            managed_biguint!(OPTED_IN_STAKE)
+        );
+    });
+
+    setup
+        .call(&sov, |sc| {
+            sc.set_max_restaking_cap(managed_biguint!(OPTED_IN_STAKE - 1));
+        })
+        .assert_user_error("Cannot set max below the current delegated amount");
+}
+
+#[test]
+fn allocation_capacity_resyncs_and_clamps_test() {
+    let mut setup = setup_with_opt_in(gravity_restaking::contract_obj);
+    allocate_to_sov(&mut setup, OPTED_IN_STAKE).assert_ok();
+
+    let validator = setup.validator.clone();
+    setup
+        .user_call(|sc| {
+            sc.revoke_delegation_from_validator(managed_address!(&validator), stake_tokens(400));
+        })
+        .assert_ok();
+    check_allocation(&mut setup, 300, 300);
+
+    setup.owner_call(|sc| {
+        sc.add_token_to_whitelist(
+            managed_token_id!(OTHER_TOKEN_ID),
+            managed_biguint!(ONE_TOKEN_IN_EGLD),
+            OptionalValue::None,
+        );
+    });
+
+    let sov = setup.sov.clone();
+    setup
+        .call(&sov, |sc| {
+            let mut tokens = MultiValueEncoded::new();
+            tokens.push(managed_token_id!(OTHER_TOKEN_ID));
+            sc.set_accepted_tokens(tokens);
+        })
+        .assert_ok();
+    check_allocation(&mut setup, 0, 0);
+
+    setup
+        .call(&sov, |sc| sc.set_accepted_tokens(MultiValueEncoded::new()))
+        .assert_ok();
+    check_allocation(&mut setup, 300, 0);
+}
+
+#[test]
+fn sov_exit_clears_allocation_opt_ins_test() {
+    let mut setup = setup_with_opt_in(gravity_restaking::contract_obj);
+    allocate_to_sov(&mut setup, OPTED_IN_STAKE).assert_ok();
+
+    let sov = setup.sov.clone();
+    setup.call(&sov, |sc| sc.unregister()).assert_ok();
+    setup
+        .call(&sov, |sc| sc.unregister())
+        .assert_user_error("Delegators still have to exit");
+
+    setup
+        .user_call(|sc| sc.exit_sov_delegators(managed_address!(&sov), 10))
+        .assert_ok();
+    check_allocation(&mut setup, 0, 0);
+
+    let user = setup.user.clone();
+    let validator = setup.validator.clone();
+    setup.query(|sc| {
+        let user_id = sc.user_ids().get_id(&managed_address!(&user));
+        let validator_id = sc.resolve_validator_id(&managed_address!(&validator));
+        assert!(sc.opted_in_sovs(user_id, validator_id).is_empty());
+    });
+
+    setup.call(&sov, |sc| sc.unregister()).assert_ok();
+}
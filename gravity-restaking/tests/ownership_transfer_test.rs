@@ -0,0 +1,80 @@
+mod restaking_setup;
+
+use gravity_restaking::{
+    user_actions::{
+        ownership_transfer::OwnershipTransferModule, sovereign::SovereignModule,
+        validator::ValidatorModule,
+    },
+    views::ViewsModule,
+};
+use multiversx_sc_scenario::{managed_address, managed_buffer, rust_biguint};
+use restaking_setup::*;
+
+#[test]
+fn validator_owner_registers_again_after_transfer_test() {
+    let mut setup = RestakingSetup::new(gravity_restaking::contract_obj);
+    let new_owner = setup.b_mock.create_user_account(&rust_biguint!(0));
+    let validator = setup.validator.clone();
+    setup
+        .call(&validator, |sc| {
+            sc.propose_validator_owner(managed_address!(&new_owner));
+        })
+        .assert_ok();
+    setup
+        .call(&new_owner, |sc| {
+            sc.accept_validator_ownership(managed_address!(&validator));
+        })
+        .assert_ok();
+
+    setup
+        .call(&validator, |sc| {
+            sc.register(managed_buffer!(b"new-validator"))
+        })
+        .assert_ok();
+
+    setup.query(|sc| {
+        let transferred_id = sc.get_validator_id(managed_address!(&new_owner));
+        let new_id = sc.get_validator_id(managed_address!(&validator));
+        assert_eq!(transferred_id, 1);
+        assert_eq!(new_id, 2);
+        assert_eq!(
+            sc.get_validator_address(transferred_id).into_option(),
+            Some(managed_address!(&new_owner))
+        );
+        assert_eq!(sc.get_summary().nr_validators, 2);
+    });
+}
+
+#[test]
+fn sov_owner_registers_again_after_transfer_test() {
+    let mut setup = RestakingSetup::new(gravity_restaking::contract_obj);
+    let new_owner = setup.b_mock.create_user_account(&rust_biguint!(0));
+    let sov = setup.sov.clone();
+    setup
+        .call(&sov, |sc| {
+            sc.propose_sov_owner(managed_address!(&new_owner))
+        })
+        .assert_ok();
+    setup
+        .call(&new_owner, |sc| {
+            sc.accept_sov_ownership(managed_address!(&sov));
+        })
+        .assert_ok();
+
+    setup
+        .call(&sov, |sc| {
+            sc.register_sov(managed_buffer!(b"new-sov"), managed_buffer!(b"description"));
+        })
+        .assert_ok();
+
+    setup.query(|sc| {
+        let transferred_id = sc.get_sov_id(managed_address!(&new_owner));
+        let new_id = sc.get_sov_id(managed_address!(&sov));
+        assert_ne!(transferred_id, new_id);
+        assert_eq!(
+            sc.get_sov_address(new_id).into_option(),
+            Some(managed_address!(&sov))
+        );
+        assert_eq!(sc.get_summary().nr_sovereigns, 3);
+    });
+}
@@ -0,0 +1,342 @@
+use gravity_restaking::{
+    token_whitelist::TokenWhitelistModule,
+    user_actions::{
+        deregistration::DeregistrationModule,
+        receipt_token::ReceiptTokenModule,
+        redelegate::RedelegateModule,
+        sov_allow_list::SovAllowListModule,
+        sovereign::SovereignModule,
+        unbond::UnbondModule,
+        user::{PaymentsMultiValue, UserModule},
+        validator::ValidatorModule,
+    },
+    GravityRestaking,
+};
+use multiversx_sc::{
+    codec::multi_types::OptionalValue,
+    types::{Address, EsdtLocalRole, MultiValue3, MultiValueEncoded},
+};
+use multiversx_sc_scenario::{
+    managed_address, managed_biguint, managed_buffer, managed_token_id, rust_biguint,
+    whitebox_legacy::{BlockchainStateWrapper, ContractObjWrapper, TxResult},
+    DebugApi,
+};
+
+static STAKE_TOKEN_ID: &[u8] = b"STAKE-123456";
+static RECEIPT_TOKEN_ID: &[u8] = b"RECEIPT-123456";
+static VALIDATOR_NAME: &[u8] = b"validator";
+static OTHER_VALIDATOR_NAME: &[u8] = b"other-validator";
+static SOV_NAME: &[u8] = b"sov";
+
+const WASM_PATH: &str = "output/gravity-restaking.wasm";
+const UNBOND_EPOCHS: u64 = 10;
+const COOLDOWN_EPOCHS: u64 = 5;
+const ONE_TOKEN_IN_EGLD: u64 = 1_000_000_000_000_000_000;
+const USER_BALANCE: u64 = 1_000;
+const TOKENIZED_AMOUNT: u64 = 400;
+const RECEIPT_NONCE: u64 = 1;
+
+struct ReceiptTokenSetup<ContractObjBuilder>
+where
+    ContractObjBuilder: 'static + Copy + Fn() -> gravity_restaking::ContractObj<DebugApi>,
+{
+    b_mock: BlockchainStateWrapper,
+    user: Address,
+    validator: Address,
+    other_validator: Address,
+    sov: Address,
+    sc_wrapper: ContractObjWrapper<gravity_restaking::ContractObj<DebugApi>, ContractObjBuilder>,
+}
+
+impl<ContractObjBuilder> ReceiptTokenSetup<ContractObjBuilder>
+where
+    ContractObjBuilder: 'static + Copy + Fn() -> gravity_restaking::ContractObj<DebugApi>,
+{
+    fn new(builder: ContractObjBuilder) -> Self {
+        let rust_zero = rust_biguint!(0);
+        let mut b_mock = BlockchainStateWrapper::new();
+        let owner = b_mock.create_user_account(&rust_zero);
+        let user = b_mock.create_user_account(&rust_zero);
+        let validator = b_mock.create_user_account(&rust_zero);
+        let other_validator = b_mock.create_user_account(&rust_zero);
+        let sov = b_mock.create_user_account(&rust_zero);
+        b_mock.set_esdt_balance(&user, STAKE_TOKEN_ID, &rust_biguint!(USER_BALANCE));
+
+        let sc_wrapper = b_mock.create_sc_account(&rust_zero, Some(&owner), builder, WASM_PATH);
+        b_mock.set_esdt_local_roles(
+            sc_wrapper.address_ref(),
+            RECEIPT_TOKEN_ID,
+            &[EsdtLocalRole::NftCreate, EsdtLocalRole::NftBurn],
+        );
+
+        b_mock
+            .execute_tx(&owner, &sc_wrapper, &rust_zero, |sc| {
+                sc.init(UNBOND_EPOCHS);
+                sc.add_token_to_whitelist(
+                    managed_token_id!(STAKE_TOKEN_ID),
+                    managed_biguint!(ONE_TOKEN_IN_EGLD),
+                    OptionalValue::None,
+                );
+                sc.set_redelegation_cooldown_epochs(COOLDOWN_EPOCHS);
+                sc.receipt_token()
+                    .set_token_id(managed_token_id!(RECEIPT_TOKEN_ID));
+            })
+            .assert_ok();
+
+        b_mock
+            .execute_tx(&validator, &sc_wrapper, &rust_zero, |sc| {
+                sc.register(managed_buffer!(VALIDATOR_NAME));
+            })
+            .assert_ok();
+
+        b_mock
+            .execute_tx(&other_validator, &sc_wrapper, &rust_zero, |sc| {
+                sc.register(managed_buffer!(OTHER_VALIDATOR_NAME));
+            })
+            .assert_ok();
+
+        b_mock
+            .execute_tx(&sov, &sc_wrapper, &rust_zero, |sc| {
+                sc.register_sov(managed_buffer!(SOV_NAME), managed_buffer!(b"description"));
+            })
+            .assert_ok();
+
+        b_mock
+            .execute_esdt_transfer(
+                &user,
+                &sc_wrapper,
+                STAKE_TOKEN_ID,
+                0,
+                &rust_biguint!(USER_BALANCE),
+                |sc| {
+                    sc.deposit();
+                },
+            )
+            .assert_ok();
+
+        Self {
+            b_mock,
+            user,
+            validator,
+            other_validator,
+            sov,
+            sc_wrapper,
+        }
+    }
+
+    fn delegate_to_validator(&mut self, amount: u64) {
+        let validator = self.validator.clone();
+        self.b_mock
+            .execute_tx(&self.user, &self.sc_wrapper, &rust_biguint!(0), |sc| {
+                sc.delegate_to_validator(managed_address!(&validator), stake_tokens(amount));
+            })
+            .assert_ok();
+    }
+
+    fn delegate_to_sov(&mut self, amount: u64) {
+        self.b_mock
+            .execute_tx(&self.user, &self.sc_wrapper, &rust_biguint!(0), |sc| {
+                sc.delegate_for_sovereign_chain(managed_buffer!(SOV_NAME), stake_tokens(amount));
+            })
+            .assert_ok();
+    }
+
+    fn tokenize_validator_delegation(&mut self, amount: u64) {
+        let validator = self.validator.clone();
+        self.b_mock
+            .execute_tx(&self.user, &self.sc_wrapper, &rust_biguint!(0), |sc| {
+                let _ = sc.tokenize_validator_delegation(
+                    managed_address!(&validator),
+                    stake_tokens(amount),
+                );
+            })
+            .assert_ok();
+    }
+
+    fn tokenize_sov_delegation(&mut self, amount: u64) {
+        self.b_mock
+            .execute_tx(&self.user, &self.sc_wrapper, &rust_biguint!(0), |sc| {
+                let _ = sc.tokenize_sov_delegation(managed_buffer!(SOV_NAME), stake_tokens(amount));
+            })
+            .assert_ok();
+    }
+
+    fn redeem_receipt(&mut self, amount: u64) -> TxResult {
+        self.b_mock.execute_esdt_transfer(
+            &self.user,
+            &self.sc_wrapper,
+            RECEIPT_TOKEN_ID,
+            RECEIPT_NONCE,
+            &rust_biguint!(amount),
+            |sc| {
+                sc.redeem_receipt();
+            },
+        )
+    }
+
+    fn sov_call(&mut self, action: impl FnOnce(gravity_restaking::ContractObj<DebugApi>)) {
+        let sov = self.sov.clone();
+        self.b_mock
+            .execute_tx(&sov, &self.sc_wrapper, &rust_biguint!(0), action)
+            .assert_ok();
+    }
+}
+
+fn stake_tokens(amount: u64) -> PaymentsMultiValue<DebugApi> {
+    let mut tokens = MultiValueEncoded::new();
+    tokens.push(MultiValue3::from((
+        managed_token_id!(STAKE_TOKEN_ID),
+        0u64,
+        managed_biguint!(amount),
+    )));
+
+    tokens
+}
+
+#[test]
+fn redeem_starts_redelegation_cooldown_test() {
+    let mut setup = ReceiptTokenSetup::new(gravity_restaking::contract_obj);
+    setup.delegate_to_validator(USER_BALANCE);
+    setup.tokenize_validator_delegation(TOKENIZED_AMOUNT);
+    assert_eq!(
+        setup
+            .b_mock
+            .get_esdt_balance(&setup.user, RECEIPT_TOKEN_ID, RECEIPT_NONCE),
+        rust_biguint!(TOKENIZED_AMOUNT)
+    );
+
+    setup.redeem_receipt(TOKENIZED_AMOUNT).assert_ok();
+    assert_eq!(
+        setup
+            .b_mock
+            .get_esdt_balance(&setup.user, RECEIPT_TOKEN_ID, RECEIPT_NONCE),
+        rust_biguint!(0)
+    );
+
+    let validator = setup.validator.clone();
+    setup
+        .b_mock
+        .execute_query(&setup.sc_wrapper, |sc| {
+            assert_eq!(
+                sc.get_total_delegated_amount(managed_address!(&validator)),
+                managed_biguint!(USER_BALANCE)
+            );
+        })
+        .assert_ok();
+
+    let other_validator = setup.other_validator.clone();
+    setup
+        .b_mock
+        .execute_tx(&setup.user, &setup.sc_wrapper, &rust_biguint!(0), |sc| {
+            sc.redelegate_validator(
+                managed_address!(&validator),
+                managed_address!(&other_validator),
+                stake_tokens(100),
+            );
+        })
+        .assert_user_error("Redelegation cooldown not finished");
+
+    setup.b_mock.set_block_epoch(COOLDOWN_EPOCHS);
+    setup
+        .b_mock
+        .execute_tx(&setup.user, &setup.sc_wrapper, &rust_biguint!(0), |sc| {
+            sc.redelegate_validator(
+                managed_address!(&validator),
+                managed_address!(&other_validator),
+                stake_tokens(100),
+            );
+        })
+        .assert_ok();
+}
+
+#[test]
+fn redeem_checks_sovereign_rules_test() {
+    let mut setup = ReceiptTokenSetup::new(gravity_restaking::contract_obj);
+    setup.delegate_to_sov(USER_BALANCE);
+    setup.tokenize_sov_delegation(TOKENIZED_AMOUNT);
+
+    setup.sov_call(|sc| sc.pause());
+    setup
+        .redeem_receipt(TOKENIZED_AMOUNT)
+        .assert_user_error("Sovereign chain does not accept delegations");
+    setup.sov_call(|sc| sc.resume());
+
+    setup.sov_call(|sc| sc.set_min_delegation(managed_biguint!(TOKENIZED_AMOUNT + 1)));
+    setup
+        .redeem_receipt(TOKENIZED_AMOUNT)
+        .assert_user_error("Delegation below the sovereign chain minimum");
+    setup.sov_call(|sc| sc.set_min_delegation(managed_biguint!(0)));
+
+    setup.sov_call(|sc| sc.set_max_per_delegator(managed_biguint!(USER_BALANCE - 1)));
+    setup
+        .redeem_receipt(TOKENIZED_AMOUNT)
+        .assert_user_error("Above the sovereign chain maximum per delegator");
+    setup.sov_call(|sc| sc.set_max_per_delegator(managed_biguint!(USER_BALANCE)));
+
+    setup.redeem_receipt(TOKENIZED_AMOUNT).assert_ok();
+}
+
+#[test]
+fn exited_validator_receipts_unbond_test() {
+    let mut setup = ReceiptTokenSetup::new(gravity_restaking::contract_obj);
+    setup.delegate_to_validator(USER_BALANCE);
+    setup.tokenize_validator_delegation(TOKENIZED_AMOUNT);
+
+    let validator = setup.validator.clone();
+    setup
+        .b_mock
+        .execute_tx(&validator, &setup.sc_wrapper, &rust_biguint!(0), |sc| {
+            sc.unregister_validator();
+        })
+        .assert_ok();
+
+    setup
+        .b_mock
+        .execute_tx(&setup.user, &setup.sc_wrapper, &rust_biguint!(0), |sc| {
+            sc.exit_validator_delegators(managed_address!(&validator), 10);
+        })
+        .assert_ok();
+
+    setup
+        .b_mock
+        .execute_tx(&validator, &setup.sc_wrapper, &rust_biguint!(0), |sc| {
+            sc.unregister_validator();
+        })
+        .assert_ok();
+
+    setup
+        .redeem_receipt(TOKENIZED_AMOUNT)
+        .assert_user_error("Receipt target exited, revoke instead");
+
+    setup
+        .b_mock
+        .execute_esdt_transfer(
+            &setup.user,
+            &setup.sc_wrapper,
+            RECEIPT_TOKEN_ID,
+            RECEIPT_NONCE,
+            &rust_biguint!(TOKENIZED_AMOUNT),
+            |sc| {
+                sc.revoke_with_receipt();
+            },
+        )
+        .assert_ok();
+
+    let user = setup.user.clone();
+    setup
+        .b_mock
+        .execute_query(&setup.sc_wrapper, |sc| {
+            let unbond_info = sc.get_user_unbond_info(managed_address!(&user));
+            assert_eq!(unbond_info.len(), 1);
+
+            let unbonding = unbond_info.get(0);
+            assert_eq!(unbonding.unbond_epoch, UNBOND_EPOCHS);
+            assert_eq!(
+                unbonding
+                    .tokens
+                    .get_amount(&managed_token_id!(STAKE_TOKEN_ID), 0),
+                managed_biguint!(USER_BALANCE)
+            );
+        })
+        .assert_ok();
+}
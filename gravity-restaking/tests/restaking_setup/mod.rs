@@ -0,0 +1,150 @@
+#![allow(dead_code)]
+
+use gravity_restaking::{
+    token_whitelist::TokenWhitelistModule,
+    user_actions::{
+        sovereign::SovereignModule,
+        user::{PaymentsMultiValue, UserModule},
+        validator::ValidatorModule,
+    },
+    GravityRestaking,
+};
+use multiversx_sc::{
+    codec::multi_types::OptionalValue,
+    types::{Address, MultiValue3, MultiValueEncoded},
+};
+use multiversx_sc_scenario::{
+    managed_biguint, managed_buffer, managed_token_id, rust_biguint,
+    whitebox_legacy::{BlockchainStateWrapper, ContractObjWrapper, TxResult},
+    DebugApi,
+};
+
+pub static STAKE_TOKEN_ID: &[u8] = b"STAKE-123456";
+pub static VALIDATOR_NAME: &[u8] = b"validator";
+pub static SOV_NAME: &[u8] = b"sov";
+pub static OTHER_SOV_NAME: &[u8] = b"other-sov";
+
+pub const WASM_PATH: &str = "output/gravity-restaking.wasm";
+pub const UNBOND_EPOCHS: u64 = 10;
+pub const ONE_TOKEN_IN_EGLD: u64 = 1_000_000_000_000_000_000;
+pub const USER_BALANCE: u64 = 1_000;
+
+pub struct RestakingSetup<ContractObjBuilder>
+where
+    ContractObjBuilder: 'static + Copy + Fn() -> gravity_restaking::ContractObj<DebugApi>,
+{
+    pub b_mock: BlockchainStateWrapper,
+    pub owner: Address,
+    pub user: Address,
+    pub validator: Address,
+    pub sov: Address,
+    pub other_sov: Address,
+    pub sc_wrapper:
+        ContractObjWrapper<gravity_restaking::ContractObj<DebugApi>, ContractObjBuilder>,
+}
+
+impl<ContractObjBuilder> RestakingSetup<ContractObjBuilder>
+where
+    ContractObjBuilder: 'static + Copy + Fn() -> gravity_restaking::ContractObj<DebugApi>,
+{
+    /// One validator and two sovereign chains are registered,
+    /// and the user has deposited the whole stake token balance
+    pub fn new(builder: ContractObjBuilder) -> Self {
+        let rust_zero = rust_biguint!(0);
+        let mut b_mock = BlockchainStateWrapper::new();
+        let owner = b_mock.create_user_account(&rust_zero);
+        let user = b_mock.create_user_account(&rust_zero);
+        let validator = b_mock.create_user_account(&rust_zero);
+        let sov = b_mock.create_user_account(&rust_zero);
+        let other_sov = b_mock.create_user_account(&rust_zero);
+        b_mock.set_esdt_balance(&user, STAKE_TOKEN_ID, &rust_biguint!(USER_BALANCE));
+
+        let sc_wrapper = b_mock.create_sc_account(&rust_zero, Some(&owner), builder, WASM_PATH);
+        b_mock
+            .execute_tx(&owner, &sc_wrapper, &rust_zero, |sc| {
+                sc.init(UNBOND_EPOCHS);
+                sc.add_token_to_whitelist(
+                    managed_token_id!(STAKE_TOKEN_ID),
+                    managed_biguint!(ONE_TOKEN_IN_EGLD),
+                    OptionalValue::None,
+                );
+            })
+            .assert_ok();
+
+        b_mock
+            .execute_tx(&validator, &sc_wrapper, &rust_zero, |sc| {
+                sc.register(managed_buffer!(VALIDATOR_NAME));
+            })
+            .assert_ok();
+
+        for (sov_address, sov_name) in [(&sov, SOV_NAME), (&other_sov, OTHER_SOV_NAME)] {
+            b_mock
+                .execute_tx(sov_address, &sc_wrapper, &rust_zero, |sc| {
+                    sc.register_sov(managed_buffer!(sov_name), managed_buffer!(b"description"));
+                })
+                .assert_ok();
+        }
+
+        b_mock
+            .execute_esdt_transfer(
+                &user,
+                &sc_wrapper,
+                STAKE_TOKEN_ID,
+                0,
+                &rust_biguint!(USER_BALANCE),
+                |sc| {
+                    sc.deposit();
+                },
+            )
+            .assert_ok();
+
+        Self {
+            b_mock,
+            owner,
+            user,
+            validator,
+            sov,
+            other_sov,
+            sc_wrapper,
+        }
+    }
+
+    pub fn call(
+        &mut self,
+        caller: &Address,
+        action: impl FnOnce(gravity_restaking::ContractObj<DebugApi>),
+    ) -> TxResult {
+        self.b_mock
+            .execute_tx(caller, &self.sc_wrapper, &rust_biguint!(0), action)
+    }
+
+    pub fn owner_call(&mut self, action: impl FnOnce(gravity_restaking::ContractObj<DebugApi>)) {
+        let owner = self.owner.clone();
+        self.call(&owner, action).assert_ok();
+    }
+
+    pub fn user_call(
+        &mut self,
+        action: impl FnOnce(gravity_restaking::ContractObj<DebugApi>),
+    ) -> TxResult {
+        let user = self.user.clone();
+        self.call(&user, action)
+    }
+
+    pub fn query(&mut self, action: impl FnOnce(gravity_restaking::ContractObj<DebugApi>)) {
+        self.b_mock
+            .execute_query(&self.sc_wrapper, action)
+            .assert_ok();
+    }
+}
+
+pub fn stake_tokens(amount: u64) -> PaymentsMultiValue<DebugApi> {
+    let mut tokens = MultiValueEncoded::new();
+    tokens.push(MultiValue3::from((
+        managed_token_id!(STAKE_TOKEN_ID),
+        0u64,
+        managed_biguint!(amount),
+    )));
+
+    tokens
+}
@@ -0,0 +1,84 @@
+mod restaking_setup;
+
+use bridge_mock::BridgeMock;
+use gravity_restaking::user_actions::{
+    operator_allocation::OperatorAllocationModule, security_snapshot::SecuritySnapshotModule,
+    user::UserModule,
+};
+use multiversx_sc_scenario::{managed_address, managed_biguint, managed_buffer, rust_biguint};
+use restaking_setup::*;
+
+const BRIDGE_WASM_PATH: &str = "../bridge-mock/output/bridge-mock.wasm";
+const OPT_IN_PERCENT: u32 = 5_000;
+const ALLOCATED_STAKE: u64 = USER_BALANCE / 2;
+
+#[test]
+fn snapshot_is_received_by_the_bridge_test() {
+    let mut setup = RestakingSetup::new(gravity_restaking::contract_obj);
+    let owner = setup.owner.clone();
+    let bridge_wrapper = setup.b_mock.create_sc_account(
+        &rust_biguint!(0),
+        Some(&owner),
+        bridge_mock::contract_obj,
+        BRIDGE_WASM_PATH,
+    );
+    setup
+        .b_mock
+        .execute_tx(&owner, &bridge_wrapper, &rust_biguint!(0), |sc| {
+            sc.init();
+        })
+        .assert_ok();
+
+    let validator = setup.validator.clone();
+    setup
+        .user_call(|sc| {
+            sc.delegate_to_validator(managed_address!(&validator), stake_tokens(USER_BALANCE));
+            sc.set_allocation_opt_in(
+                managed_address!(&validator),
+                managed_buffer!(SOV_NAME),
+                OPT_IN_PERCENT,
+            );
+        })
+        .assert_ok();
+    setup
+        .call(&validator, |sc| {
+            sc.allocate_to_sovereign(managed_buffer!(SOV_NAME), managed_biguint!(ALLOCATED_STAKE));
+        })
+        .assert_ok();
+
+    let sov = setup.sov.clone();
+    let bridge = bridge_wrapper.address_ref().clone();
+    setup
+        .call(&sov, |sc| sc.set_security_bridge(managed_address!(&bridge)))
+        .assert_ok();
+    setup
+        .call(&sov, |sc| sc.send_security_snapshot(managed_address!(&sov)))
+        .assert_ok();
+
+    let gravity_restaking = setup.sc_wrapper.address_ref().clone();
+    setup
+        .b_mock
+        .execute_query(&bridge_wrapper, |sc| {
+            let snapshot = sc
+                .last_snapshot(&managed_address!(&gravity_restaking))
+                .get();
+            assert_eq!(snapshot.version, bridge_mock::SNAPSHOT_FORMAT_VERSION);
+            assert_eq!(snapshot.nonce, 1);
+            assert_eq!(snapshot.total_stake, managed_biguint!(ALLOCATED_STAKE));
+            assert_eq!(snapshot.validators.len(), 1);
+
+            let validator_security = snapshot.validators.get(0);
+            assert_eq!(validator_security.validator, managed_address!(&validator));
+            assert_eq!(validator_security.stake, managed_biguint!(ALLOCATED_STAKE));
+        })
+        .assert_ok();
+
+    // anyone else has to wait for the snapshot interval
+    let user = setup.user.clone();
+    setup.owner_call(|sc| sc.set_snapshot_interval_epochs(5));
+    setup
+        .call(&user, |sc| {
+            sc.send_security_snapshot(managed_address!(&sov))
+        })
+        .assert_user_error("Snapshot interval not passed");
+}
@@ -0,0 +1,128 @@
+mod restaking_setup;
+
+use gravity_restaking::user_actions::{
+    shared_security::SharedSecurityModule, sovereign::SovereignModule,
+};
+use multiversx_sc_scenario::{managed_address, managed_biguint, managed_buffer, managed_token_id};
+use restaking_setup::*;
+
+const SHARED_MULTIPLIER: u32 = 20_000;
+
+fn setup_with_shared_stake<ContractObjBuilder>(
+    builder: ContractObjBuilder,
+) -> RestakingSetup<ContractObjBuilder>
+where
+    ContractObjBuilder:
+        'static + Copy + Fn() -> gravity_restaking::ContractObj<multiversx_sc_scenario::DebugApi>,
+{
+    let mut setup = RestakingSetup::new(builder);
+    setup.owner_call(|sc| sc.set_shared_security_multiplier(SHARED_MULTIPLIER));
+    setup
+        .user_call(|sc| sc.lock_shared_stake(stake_tokens(USER_BALANCE)))
+        .assert_ok();
+
+    setup
+}
+
+#[test]
+fn shared_security_multiplier_cap_test() {
+    let mut setup = RestakingSetup::new(gravity_restaking::contract_obj);
+    let owner = setup.owner.clone();
+    setup
+        .call(&owner, |sc| sc.set_shared_security_multiplier(9_999))
+        .assert_user_error("Invalid multiplier");
+    setup
+        .call(&owner, |sc| sc.set_shared_security_multiplier(50_001))
+        .assert_user_error("Invalid multiplier");
+
+    setup.owner_call(|sc| sc.set_shared_security_multiplier(50_000));
+    setup.query(|sc| assert_eq!(sc.shared_security_multiplier().get(), 50_000));
+}
+
+#[test]
+fn shared_stake_backs_multiple_sovs_test() {
+    let mut setup = setup_with_shared_stake(gravity_restaking::contract_obj);
+    setup
+        .user_call(|sc| {
+            sc.allocate_shared_stake(managed_buffer!(SOV_NAME), stake_tokens(USER_BALANCE))
+        })
+        .assert_ok();
+    setup
+        .user_call(|sc| {
+            sc.allocate_shared_stake(managed_buffer!(OTHER_SOV_NAME), stake_tokens(USER_BALANCE))
+        })
+        .assert_ok();
+
+    let sov = setup.sov.clone();
+    let other_sov = setup.other_sov.clone();
+    setup.query(|sc| {
+        assert_eq!(
+            sc.get_total_delegated_sov_amount(managed_address!(&sov)),
+            managed_biguint!(USER_BALANCE)
+        );
+        assert_eq!(
+            sc.get_total_delegated_sov_amount(managed_address!(&other_sov)),
+            managed_biguint!(USER_BALANCE)
+        );
+    });
+
+    setup
+        .user_call(|sc| sc.allocate_shared_stake(managed_buffer!(SOV_NAME), stake_tokens(1)))
+        .assert_user_error("Shared security multiplier exceeded");
+    setup
+        .user_call(|sc| sc.unlock_shared_stake(stake_tokens(1)))
+        .assert_user_error("Shared security multiplier exceeded");
+}
+
+#[test]
+fn deallocated_stake_covers_allocations_until_released_test() {
+    let mut setup = setup_with_shared_stake(gravity_restaking::contract_obj);
+    for sov_name in [SOV_NAME, OTHER_SOV_NAME] {
+        setup
+            .user_call(|sc| {
+                sc.allocate_shared_stake(managed_buffer!(sov_name), stake_tokens(USER_BALANCE))
+            })
+            .assert_ok();
+    }
+
+    setup
+        .user_call(|sc| {
+            sc.deallocate_shared_stake(managed_buffer!(OTHER_SOV_NAME), stake_tokens(USER_BALANCE))
+        })
+        .assert_ok();
+
+    let other_sov = setup.other_sov.clone();
+    setup.query(|sc| {
+        assert_eq!(
+            sc.get_total_delegated_sov_amount(managed_address!(&other_sov)),
+            managed_biguint!(0)
+        );
+    });
+
+    let half = USER_BALANCE / 2;
+    setup
+        .user_call(|sc| sc.unlock_shared_stake(stake_tokens(half)))
+        .assert_user_error("Shared security multiplier exceeded");
+
+    setup.b_mock.set_block_epoch(UNBOND_EPOCHS);
+    setup
+        .user_call(|sc| sc.unlock_shared_stake(stake_tokens(half)))
+        .assert_user_error("Allocation exceeds locked stake");
+
+    setup
+        .user_call(|sc| sc.deallocate_shared_stake(managed_buffer!(SOV_NAME), stake_tokens(half)))
+        .assert_ok();
+    setup.b_mock.set_block_epoch(2 * UNBOND_EPOCHS);
+    setup
+        .user_call(|sc| sc.unlock_shared_stake(stake_tokens(half)))
+        .assert_ok();
+
+    let user = setup.user.clone();
+    setup.query(|sc| {
+        let shared_stake = sc.get_shared_stake(managed_address!(&user));
+        assert_eq!(
+            shared_stake.get_amount(&managed_token_id!(STAKE_TOKEN_ID), 0),
+            managed_biguint!(half)
+        );
+    });
+}
@@ -0,0 +1,104 @@
+mod restaking_setup;
+
+use gravity_restaking::{
+    user_actions::{
+        common_storage::CommonStorageModule, user::UserModule, validator::ValidatorModule,
+    },
+    views::ViewsModule,
+};
+use multiversx_sc_scenario::{managed_address, managed_biguint, DebugApi};
+use restaking_setup::*;
+
+const DELEGATED: u64 = 600;
+const REVOKED: u64 = 200;
+
+fn check_stake_at_epoch<ContractObjBuilder>(
+    setup: &mut RestakingSetup<ContractObjBuilder>,
+    epoch: u64,
+    expected_stake: u64,
+) where
+    ContractObjBuilder: 'static + Copy + Fn() -> gravity_restaking::ContractObj<DebugApi>,
+{
+    let user = setup.user.clone();
+    let validator = setup.validator.clone();
+    setup.query(|sc| {
+        assert_eq!(
+            sc.get_validator_stake_at_epoch(managed_address!(&validator), epoch),
+            managed_biguint!(expected_stake)
+        );
+        assert_eq!(
+            sc.get_user_validator_stake_at_epoch(
+                managed_address!(&user),
+                managed_address!(&validator),
+                epoch
+            ),
+            managed_biguint!(expected_stake)
+        );
+    });
+}
+
+fn delegate<ContractObjBuilder>(setup: &mut RestakingSetup<ContractObjBuilder>, amount: u64)
+where
+    ContractObjBuilder: 'static + Copy + Fn() -> gravity_restaking::ContractObj<DebugApi>,
+{
+    let validator = setup.validator.clone();
+    setup
+        .user_call(|sc| {
+            sc.delegate_to_validator(managed_address!(&validator), stake_tokens(amount));
+        })
+        .assert_ok();
+}
+
+fn revoke<ContractObjBuilder>(setup: &mut RestakingSetup<ContractObjBuilder>, amount: u64)
+where
+    ContractObjBuilder: 'static + Copy + Fn() -> gravity_restaking::ContractObj<DebugApi>,
+{
+    let validator = setup.validator.clone();
+    setup
+        .user_call(|sc| {
+            sc.revoke_delegation_from_validator(managed_address!(&validator), stake_tokens(amount));
+        })
+        .assert_ok();
+}
+
+#[test]
+fn stake_changes_apply_from_the_next_epoch_test() {
+    let mut setup = RestakingSetup::new(gravity_restaking::contract_obj);
+    setup.b_mock.set_block_epoch(1);
+    delegate(&mut setup, DELEGATED);
+    check_stake_at_epoch(&mut setup, 1, 0);
+    check_stake_at_epoch(&mut setup, 2, DELEGATED);
+
+    setup.b_mock.set_block_epoch(3);
+    revoke(&mut setup, REVOKED);
+    delegate(&mut setup, REVOKED / 2);
+    check_stake_at_epoch(&mut setup, 0, 0);
+    check_stake_at_epoch(&mut setup, 3, DELEGATED);
+    check_stake_at_epoch(&mut setup, 4, DELEGATED - REVOKED / 2);
+    check_stake_at_epoch(&mut setup, 100, DELEGATED - REVOKED / 2);
+}
+
+#[test]
+fn stake_from_before_the_checkpoints_is_backfilled_test() {
+    let mut setup = RestakingSetup::new(gravity_restaking::contract_obj);
+    setup.b_mock.set_block_epoch(1);
+    delegate(&mut setup, DELEGATED);
+
+    // as if delegated before the checkpoints were recorded
+    let user = setup.user.clone();
+    let validator = setup.validator.clone();
+    setup.owner_call(|sc| {
+        let user_id = sc.user_ids().get_id(&managed_address!(&user));
+        let validator_id = sc.resolve_validator_id(&managed_address!(&validator));
+        sc.validator_stake_checkpoints(validator_id).clear();
+        sc.user_validator_stake_checkpoints(user_id, validator_id)
+            .clear();
+    });
+    check_stake_at_epoch(&mut setup, 0, DELEGATED);
+
+    setup.b_mock.set_block_epoch(5);
+    revoke(&mut setup, REVOKED);
+    check_stake_at_epoch(&mut setup, 0, DELEGATED);
+    check_stake_at_epoch(&mut setup, 5, DELEGATED);
+    check_stake_at_epoch(&mut setup, 6, DELEGATED - REVOKED);
+}
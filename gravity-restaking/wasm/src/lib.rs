@@ -5,9 +5,9 @@
 ////////////////////////////////////////////////////
 
 // Init:                                 1
-// Endpoints:                           34
+// Endpoints:                          160
 // Async Callback:                       1
-// Total number of exported functions:  36
+// Total number of exported functions: 162
 
 #![no_std]
 #![allow(internal_features)]
@@ -22,38 +22,164 @@ multiversx_sc_wasm_adapter::endpoints! {
         init => init
         upgrade => upgrade
         moveStakeToReStaking => move_stake_to_re_staking
+        claimDelegationRewards => claim_delegation_rewards
+        collectDelegationRewards => collect_delegation_rewards
         addTokenToWhitelist => add_token_to_whitelist
         removeTokenFromWhitelist => remove_token_from_whitelist
         getTokenDecimals => get_token_decimals
         getTokenWhitelist => token_whitelist
         getStakedEgldForOneToken => staked_egld_for_one_token
+        setProtocolFee => set_protocol_fee
+        setTreasury => set_treasury
+        claimProtocolFees => claim_protocol_fees
+        getAccruedProtocolFees => get_accrued_protocol_fees
+        getTreasury => get_treasury
+        getProtocolFee => protocol_fee
         deposit => deposit
         withdraw => withdraw
         withdrawAll => withdraw_all
         delegateToValidator => delegate_to_validator
         delegateForSovereignChain => delegate_for_sovereign_chain
+        depositAndDelegateToValidator => deposit_and_delegate_to_validator
+        depositAndDelegateForSovereignChain => deposit_and_delegate_for_sovereign_chain
         revokeDelegationFromValidator => revoke_delegation_from_validator
         revokeDelegationFromSovereignChain => revoke_delegation_from_sovereign_chain
+        seedTotalDeposited => seed_total_deposited
         unbondTokensCaller => unbond_tokens_caller
         unbondTokensGravityRestaking => unbond_tokens_gravity_restaking
+        setRedelegationCooldownEpochs => set_redelegation_cooldown_epochs
+        redelegateValidator => redelegate_validator
+        redelegateSov => redelegate_sov
+        getValidatorRedelegationCooldownEnd => get_validator_redelegation_cooldown_end
+        getSovRedelegationCooldownEnd => get_sov_redelegation_cooldown_end
+        getRedelegationCooldownEpochs => redelegation_cooldown_epochs
+        setSharedSecurityMultiplier => set_shared_security_multiplier
+        lockSharedStake => lock_shared_stake
+        unlockSharedStake => unlock_shared_stake
+        allocateSharedStake => allocate_shared_stake
+        deallocateSharedStake => deallocate_shared_stake
+        getSharedStake => get_shared_stake
+        getSharedAllocation => get_shared_allocation
+        getPendingSharedDeallocations => get_pending_shared_deallocations
+        getSharedSecurityMultiplier => shared_security_multiplier
+        setAllocationOptIn => set_allocation_opt_in
+        allocateToSovereign => allocate_to_sovereign
+        setAcceptedTokens => set_accepted_tokens
+        getAllocationCapacity => get_allocation_capacity
+        syncAllocationOptIn => sync_allocation_opt_in
+        getOperatorAllocations => get_operator_allocations
+        getDelegatorExposure => get_delegator_exposure
+        proposeValidatorOwner => propose_validator_owner
+        acceptValidatorOwnership => accept_validator_ownership
+        proposeSovOwner => propose_sov_owner
+        acceptSovOwnership => accept_sov_ownership
+        getPendingValidatorOwner => get_pending_validator_owner
+        getPendingSovOwner => get_pending_sov_owner
+        issueReceiptToken => issue_receipt_token
+        tokenizeValidatorDelegation => tokenize_validator_delegation
+        tokenizeSovDelegation => tokenize_sov_delegation
+        redeemReceipt => redeem_receipt
+        revokeWithReceipt => revoke_with_receipt
+        getReceiptPosition => get_receipt_position
+        getReceiptTokenId => receipt_token
+        setSnapshotIntervalEpochs => set_snapshot_interval_epochs
+        setSecurityBridge => set_security_bridge
+        sendSecuritySnapshot => send_security_snapshot
+        getSecuritySnapshot => get_security_snapshot
+        getSecurityBridge => get_security_bridge
+        getSnapshotIntervalEpochs => snapshot_interval_epochs
+        unRegisterValidator => unregister_validator
+        unRegister => unregister
+        exitValidatorDelegators => exit_validator_delegators
+        exitSovDelegators => exit_sov_delegators
         register => register
         addKeys => add_keys
         removeKeys => remove_keys
+        migrateBlsKeys => migrate_bls_keys
+        setFeeChangeDelayEpochs => set_fee_change_delay_epochs
+        setMaxFeeIncrease => set_max_fee_increase
         setUpFee => set_up_fee
         setMaxDelegation => set_max_delegation
         addOwnDelegation => add_own_delegation
         getValidatorConfig => get_validator_config
+        getPendingFee => get_pending_fee
+        getBlsKeys => get_bls_keys
+        getValidatorForBlsKey => get_validator_for_bls_key
         getTotalDelegatedAmount => get_total_delegated_amount
+        getFeeChangeDelayEpochs => fee_change_delay_epochs
+        getMaxFeeIncrease => max_fee_increase
+        setMinSelfBond => set_min_self_bond
+        setMinSelfBondPercent => set_min_self_bond_percent
+        getSelfBond => get_self_bond
+        isSelfBondCompliant => is_self_bond_compliant
+        getMinSelfBond => min_self_bond
+        getMinSelfBondPercent => min_self_bond_percent
+        revokeOwnDelegation => revoke_own_delegation
+        withdrawOwnSecurityFunds => withdraw_own_security_funds
+        setValidatorMetadata => set_validator_metadata
+        changeName => change_name
+        getValidatorMetadata => get_validator_metadata
+        unstakeKeys => unstake_keys
+        restakeKeys => restake_keys
+        activateKeys => activate_keys
+        jailKeys => jail_keys
+        unjailKeys => unjail_keys
+        unjailKeysForSov => unjail_keys_for_sov
+        isBlsKeyJailedForSov => is_bls_key_jailed_for_sov
+        getBlsKeyStatus => get_bls_key_status
+        getBlsKeysByStatus => get_bls_keys_by_status
         registerSov => register_sov
+        updateSovInfo => update_sov_info
         setUpRewards => set_up_rewards
-        unRegister => unregister
         addRewards => add_rewards
         addOwnSecurityFunds => add_own_security_funds
         setMaxReStakingCap => set_max_restaking_cap
+        setMinDelegation => set_min_delegation
+        setMinTotalSecurity => set_min_total_security
+        pause => pause
+        resume => resume
+        setTargetValidatorCount => set_target_validator_count
         getSovInfo => get_sov_info
+        getTotalDelegatedSovAmount => get_total_delegated_sov_amount
+        getSovAcceptedTokens => get_sov_accepted_tokens
+        getSovState => get_sov_state
+        hasMinTotalSecurity => has_min_total_security
+        setAllowListEnabled => set_allow_list_enabled
+        addToAllowList => add_to_allow_list
+        removeFromAllowList => remove_from_allow_list
+        setMaxPerDelegator => set_max_per_delegator
+        isAllowListEnabled => is_allow_list_enabled
+        getSovAllowList => get_sov_allow_list
+        getMaxPerDelegator => get_max_per_delegator
         getUserTokens => user_tokens
+        setAutoCompound => set_auto_compound
+        getDelegationContracts => get_delegation_contracts
+        getMovedStake => get_moved_stake
+        getPendingDelegationRewards => get_pending_delegation_rewards
+        isAutoCompound => is_auto_compound
         setUnbondEpochs => set_unbond_epochs
         getUserUnbondInfo => get_user_unbond_info
+        backfillUserTargets => backfill_user_targets
+        getValidators => get_validators
+        getSovereigns => get_sovereigns
+        getValidatorDelegators => get_validator_delegators
+        getSovDelegators => get_sov_delegators
+        getSummary => get_summary
+        getUserPortfolio => get_user_portfolio
+        getUserTokensByAddress => get_user_tokens_by_address
+        getDelegatedToValidator => get_delegated_to_validator
+        getDelegatedToSov => get_delegated_to_sov
+        getValidatorStakeAtEpoch => get_validator_stake_at_epoch
+        getSovStakeAtEpoch => get_sov_stake_at_epoch
+        getUserValidatorStakeAtEpoch => get_user_validator_stake_at_epoch
+        getUserSovStakeAtEpoch => get_user_sov_stake_at_epoch
+        getUserId => get_user_id
+        getUserAddress => get_user_address
+        getValidatorId => get_validator_id
+        getValidatorAddress => get_validator_address
+        getSovId => get_sov_id
+        getSovAddress => get_sov_address
+        getSovAddressForName => get_sov_address_for_name
     )
 }
 